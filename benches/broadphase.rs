@@ -0,0 +1,169 @@
+//! Measures the frame time of the `CollisionSystem`, with hundreds of bullets and enemies
+//! flying over the terrain of a level.
+//!
+//! Each frame moves the bullets and the enemies, then runs the system on the world, the terrain
+//! having been added to the `SpatialGrid` once like when a map is loaded.
+//!
+//! Run with `cargo bench --bench broadphase`.
+
+use std::time::{Duration, Instant};
+
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Builder, Join, ReadStorage, RunNow, World, WorldExt, WriteStorage},
+};
+
+use space_menace::{
+    components::{Boundary, Collidee, Collider, CollisionLayers, Motion},
+    resources::{SpatialGrid, STEP_SECONDS},
+    systems::CollisionSystem,
+};
+
+/// Size of the level, in world units.
+const LEVEL_WIDTH: f32 = 4608.;
+const LEVEL_HEIGHT: f32 = 704.;
+
+/// Number of terrain blocks in the level.
+const TERRAIN_COUNT: usize = 64;
+
+/// Frames run before the measures, and measured.
+const WARM_UP_FRAMES: usize = 60;
+const MEASURED_FRAMES: usize = 600;
+
+/// Creates a level with `mover_count` moving colliders, half of them bullets and half of them
+/// enemies, at pseudo random positions.
+fn create_world(mover_count: usize) -> World {
+    let mut world = World::new();
+    world.register::<Boundary>();
+    world.register::<Collidee>();
+    world.register::<Collider>();
+    world.register::<Motion>();
+    world.insert(SpatialGrid::default());
+
+    let mut seed = 0x2545_f491_u32;
+    let mut random = move || {
+        // xorshift, good enough to scatter the colliders and reproducible from run to run.
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / std::u32::MAX as f32
+    };
+
+    for i in 0..TERRAIN_COUNT {
+        let mut collider = Collider::new(128., 32.);
+        let bbox = &mut collider.bounding_box;
+        bbox.position = Vector2::new(
+            (i as f32 + 0.5) * LEVEL_WIDTH / TERRAIN_COUNT as f32,
+            random() * LEVEL_HEIGHT,
+        );
+        bbox.old_position = bbox.position;
+        let (min, max) = collider.bounds();
+        let entity = world
+            .create_entity()
+            .with(collider)
+            .with(Motion::new())
+            .build();
+        world
+            .write_resource::<SpatialGrid>()
+            .insert_static(entity, min, max);
+    }
+
+    for i in 0..mover_count {
+        let is_bullet = i % 2 == 0;
+        let direction = if random() > 0.5 { 1. } else { -1. };
+        let mut motion = Motion::new();
+        let mut collider = if is_bullet {
+            motion.velocity = Vector2::new(1200. * direction, 0.);
+            let mut collider = Collider::new(44., 8.);
+            collider.set_layers(
+                CollisionLayers::PLAYER_PROJECTILE,
+                CollisionLayers::TERRAIN | CollisionLayers::ENEMY,
+            );
+            collider.is_continuous = true;
+            collider
+        } else {
+            motion.velocity = Vector2::new(180. * direction, -36.);
+            let mut collider = Collider::new(80., 60.);
+            collider.set_layers(
+                CollisionLayers::ENEMY,
+                CollisionLayers::TERRAIN | CollisionLayers::ENEMY,
+            );
+            collider
+        };
+        let bbox = &mut collider.bounding_box;
+        bbox.position = Vector2::new(random() * LEVEL_WIDTH, random() * LEVEL_HEIGHT);
+        bbox.old_position = bbox.position;
+        world
+            .create_entity()
+            .with(collider)
+            .with(motion)
+            .with(Collidee::default())
+            .with(Boundary::new(0., LEVEL_WIDTH, LEVEL_HEIGHT, 0.))
+            .build();
+    }
+    world
+}
+
+/// Moves the bullets and the enemies by one physics step.
+fn move_colliders(world: &World) {
+    let (mut colliders, mut collidees, boundaries, motions): (
+        WriteStorage<Collider>,
+        WriteStorage<Collidee>,
+        ReadStorage<Boundary>,
+        ReadStorage<Motion>,
+    ) = world.system_data();
+    for (collider, collidee, _, motion) in
+        (&mut colliders, &mut collidees, &boundaries, &motions).join()
+    {
+        *collidee = Collidee::default();
+        let bbox = &mut collider.bounding_box;
+        let moved = bbox.position + motion.velocity * STEP_SECONDS;
+        // Wrap around the level, so that the density stays the same from frame to frame,
+        // without sweeping across the whole level.
+        let wrapped = Vector2::new(
+            moved.x.rem_euclid(LEVEL_WIDTH),
+            moved.y.rem_euclid(LEVEL_HEIGHT),
+        );
+        bbox.old_position = if wrapped == moved {
+            bbox.position
+        } else {
+            wrapped
+        };
+        bbox.position = wrapped;
+        collider.hit_box.position = wrapped;
+    }
+}
+
+/// Returns the mean and the worst time spent in the `CollisionSystem` per frame.
+fn measure(mover_count: usize) -> (Duration, Duration) {
+    let mut world = create_world(mover_count);
+    let mut system = CollisionSystem;
+    system.setup(&mut world);
+
+    let mut total = Duration::default();
+    let mut worst = Duration::default();
+    for frame in 0..WARM_UP_FRAMES + MEASURED_FRAMES {
+        move_colliders(&world);
+        let start = Instant::now();
+        system.run_now(&world);
+        let elapsed = start.elapsed();
+        world.maintain();
+        if frame >= WARM_UP_FRAMES {
+            total += elapsed;
+            worst = worst.max(elapsed);
+        }
+    }
+    (total / MEASURED_FRAMES as u32, worst)
+}
+
+fn main() {
+    for &mover_count in [100, 300, 600, 1000].iter() {
+        let (mean, worst) = measure(mover_count);
+        println!(
+            "collision_system/{:<5} mean {:>8.3} ms    worst {:>8.3} ms",
+            mover_count,
+            mean.as_secs_f64() * 1000.,
+            worst.as_secs_f64() * 1000.,
+        );
+    }
+}
@@ -21,6 +21,8 @@ pub enum AnimationId {
     Move,
     Idle,
     Shoot,
+    RunShoot,
+    JumpShoot,
     Walk,
     Flying,
 }
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Component, DenseVecStorage},
+};
+
+use crate::resources::AssetType;
+
+/// What a running node of a behavior tree remembers until the next step.
+#[derive(Clone, Copy, Debug)]
+pub enum NodeMemory {
+    /// Index of the child a `Steps` node resumes from.
+    Step(usize),
+    /// Time a `Wait` has been waiting for, in seconds.
+    Waited(f32),
+    /// Unit vector a `Swoop` dives along, and the altitude it stops at.
+    Swoop { heading: Vector2<f32>, floor: f32 },
+}
+
+/// Makes an enemy act out one of the trees of the `BehaviorLibrary`.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Behavior {
+    /// Name of the tree, its file name in `assets/behaviors` without the extension.
+    pub tree: String,
+    /// Kind of enemy, whose speed and senses are tuned in the `GameplayConfig`.
+    pub kind: AssetType,
+    /// Where the enemy spawned, hovering enemies bob around its altitude.
+    pub home: Vector2<f32>,
+    /// Progress along the hovering sine wave, in radians.
+    pub hover_phase: f32,
+    /// Memory of the nodes left running by the last step, by index in the tree.
+    pub memory: HashMap<usize, NodeMemory>,
+    /// Time left before each `Cooldown` node lets its child run again, in seconds.
+    pub cooldowns: HashMap<usize, f32>,
+    /// Separation steering added to the velocity during the last step, taken back out before
+    /// the tree runs again so that it never builds up.
+    pub steering: Vector2<f32>,
+}
+
+impl Behavior {
+    pub fn new(tree: String, kind: AssetType, home: Vector2<f32>) -> Self {
+        Behavior {
+            tree,
+            kind,
+            home,
+            hover_phase: 0.,
+            memory: HashMap::new(),
+            cooldowns: HashMap::new(),
+            steering: Vector2::new(0., 0.),
+        }
+    }
+}
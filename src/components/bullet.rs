@@ -1,9 +1,24 @@
-use amethyst::ecs::{Component, NullStorage};
+use amethyst::ecs::{Component, DenseVecStorage, NullStorage};
 
 #[derive(Component, Default)]
 #[storage(NullStorage)]
 pub struct BulletImpact;
 
-#[derive(Component, Default)]
-#[storage(NullStorage)]
-pub struct Bullet;
+/// Side a projectile was fired by. It only hurts the other side.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Faction {
+    Player,
+    Enemy,
+}
+
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Bullet {
+    pub faction: Faction,
+}
+
+impl Bullet {
+    pub fn new(faction: Faction) -> Self {
+        Bullet { faction }
+    }
+}
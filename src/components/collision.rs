@@ -1,8 +1,12 @@
+use std::ops::BitOr;
+
 use amethyst::{
     core::math::Vector2,
-    ecs::{Component, DenseVecStorage},
+    ecs::{Component, DenseVecStorage, Entity},
 };
 
+use crate::resources::{CollisionAxis, CollisionEvent};
+
 #[derive(Component)]
 #[storage(DenseVecStorage)]
 pub struct Boundary {
@@ -23,6 +27,55 @@ impl Boundary {
     }
 }
 
+/// Set of collision layers. Used both for the layers a collider belongs to and for the layers
+/// it collides with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CollisionLayers(u32);
+
+impl CollisionLayers {
+    pub const NONE: Self = CollisionLayers(0);
+    pub const TERRAIN: Self = CollisionLayers(1);
+    pub const PLAYER: Self = CollisionLayers(1 << 1);
+    pub const ENEMY: Self = CollisionLayers(1 << 2);
+    pub const PLAYER_PROJECTILE: Self = CollisionLayers(1 << 3);
+    pub const ENEMY_PROJECTILE: Self = CollisionLayers(1 << 4);
+    pub const PICKUP: Self = CollisionLayers(1 << 5);
+    pub const TRIGGER: Self = CollisionLayers(1 << 6);
+    pub const ALL: Self = CollisionLayers(std::u32::MAX);
+
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Parses layer names separated by `|`, the way they are written in the map properties,
+    /// e.g. `terrain|player_projectile`.
+    pub fn parse(names: &str) -> Option<Self> {
+        names.split('|').try_fold(Self::NONE, |layers, name| {
+            let layer = match name.trim() {
+                "none" => Self::NONE,
+                "terrain" => Self::TERRAIN,
+                "player" => Self::PLAYER,
+                "enemy" => Self::ENEMY,
+                "player_projectile" => Self::PLAYER_PROJECTILE,
+                "enemy_projectile" => Self::ENEMY_PROJECTILE,
+                "pickup" => Self::PICKUP,
+                "trigger" => Self::TRIGGER,
+                "all" => Self::ALL,
+                _ => return None,
+            };
+            Some(layers | layer)
+        })
+    }
+}
+
+impl BitOr for CollisionLayers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        CollisionLayers(self.0 | other.0)
+    }
+}
+
 #[derive(Clone)]
 pub struct GenericBox {
     pub half_size: Vector2<f32>,
@@ -48,12 +101,50 @@ impl GenericBox {
             old_position: Vector2::new(0., 0.),
         }
     }
+
+    /// Moves this box from its old position to its current one, `other` doing the same, and
+    /// returns when they first touch along with the contact normal, pointing away from `other`.
+    ///
+    /// The time of impact goes from `0.`, at the old positions, to `1.`, at the current ones.
+    /// Boxes already overlapping at their old positions touch at `0.` with a zero normal.
+    pub fn sweep(&self, other: &GenericBox) -> Option<(f32, Vector2<f32>)> {
+        // Work in the frame of `other`, which then stands still.
+        let start = self.old_position - other.old_position;
+        let displacement =
+            (self.position - self.old_position) - (other.position - other.old_position);
+        let extent = self.half_size + other.half_size;
+
+        let mut time_enter = 0.;
+        let mut time_exit: f32 = 1.;
+        let mut normal = Vector2::new(0., 0.);
+        for axis in 0..2 {
+            if displacement[axis] == 0. {
+                // The boxes have to overlap on this axis the whole time.
+                if start[axis].abs() >= extent[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let direction = displacement[axis].signum();
+            let time_near = (-extent[axis] * direction - start[axis]) / displacement[axis];
+            let time_far = (extent[axis] * direction - start[axis]) / displacement[axis];
+            if time_near > time_enter {
+                time_enter = time_near;
+                normal = Vector2::new(0., 0.);
+                normal[axis] = -direction;
+            }
+            time_exit = time_exit.min(time_far);
+            if time_enter >= time_exit {
+                return None;
+            }
+        }
+        Some((time_enter, normal))
+    }
 }
 
+/// How far an entity has to be moved back along one axis to resolve its collision.
+/// The gameplay side of the collisions is handled through `CollisionEvent`s.
 pub struct CollideeDetails {
-    pub name: String,
-    pub position: Vector2<f32>,
-    pub half_size: Vector2<f32>,
     pub correction: f32,
 }
 
@@ -74,15 +165,19 @@ impl Default for Collidee {
 }
 
 impl Collidee {
+    /// Keeps the details needed to move `entity_a` out of `entity_b` and returns the collision
+    /// as an event for the gameplay systems.
+    #[allow(clippy::too_many_arguments)]
     pub fn set_collidee_details(
         &mut self,
-        name: String,
+        entity_a: Entity,
+        entity_b: Entity,
         collider_a: &Collider,
         collider_b: &Collider,
         velocity_a: Vector2<f32>,
         velocity_b: Vector2<f32>,
         use_hit_box: bool,
-    ) {
+    ) -> CollisionEvent {
         let (box_a, box_b) = if use_hit_box {
             (&collider_a.hit_box, &collider_b.hit_box)
         } else {
@@ -115,36 +210,87 @@ impl Collidee {
 
         let same_direction = velocity_a.x * velocity_b.x > 0.;
         let faster = speed_ratio_a.x.abs() > speed_ratio_b.x.abs();
-        if (y_overlapped || overlap.x.abs() <= overlap.y.abs()) && !x_overlapped {
+        let axis = if (y_overlapped || overlap.x.abs() <= overlap.y.abs()) && !x_overlapped {
             if faster || !same_direction {
                 correction.x = overlap.x * speed_ratio_a.x;
             }
             // No correction (correction = 0.) is required if collider is slower
             // and both bodies are moving in the same direction
             self.horizontal = Some(CollideeDetails {
-                name,
-                position: box_b.position,
-                half_size: box_b.half_size,
                 correction: correction.x,
             });
+            CollisionAxis::Horizontal
         } else if x_overlapped && y_overlapped {
             // Might happen when an entity is added at run time.
             // As per the current game design, no correction (correction = 0.) is required for this scenario
             // This might have to be changed in future
             self.horizontal = Some(CollideeDetails {
-                name,
-                position: box_b.position,
-                half_size: box_b.half_size,
                 correction: correction.x,
             });
+            CollisionAxis::Horizontal
         } else {
             correction.y = overlap.y * speed_ratio_a.y;
             self.vertical = Some(CollideeDetails {
-                name,
-                position: box_b.position,
-                half_size: box_b.half_size,
                 correction: correction.y,
             });
+            CollisionAxis::Vertical
+        };
+
+        let (penetration, normal) = match axis {
+            CollisionAxis::Horizontal => (
+                overlap.x,
+                Vector2::new((box_a.position.x - box_b.position.x).signum(), 0.),
+            ),
+            CollisionAxis::Vertical => (
+                overlap.y,
+                Vector2::new(0., (box_a.position.y - box_b.position.y).signum()),
+            ),
+        };
+        CollisionEvent {
+            entity: entity_a,
+            other: Some(entity_b),
+            axis,
+            penetration,
+            normal,
+            contact_position: collider_a.bounding_box.position + normal * penetration,
+        }
+    }
+
+    /// Keeps the details needed to move `entity_a` back to where it touched `entity_b`, as found
+    /// by `GenericBox::sweep`, and returns the collision as an event for the gameplay systems.
+    pub fn set_swept_collidee_details(
+        &mut self,
+        entity_a: Entity,
+        entity_b: Entity,
+        collider_a: &Collider,
+        time_of_impact: f32,
+        normal: Vector2<f32>,
+    ) -> CollisionEvent {
+        let bbox = &collider_a.bounding_box;
+        let contact_position =
+            bbox.old_position + (bbox.position - bbox.old_position) * time_of_impact;
+        let correction = bbox.position - contact_position;
+
+        // Boxes which already overlapped have no normal, they are handled as horizontal
+        // collisions like in `set_collidee_details`.
+        let (axis, correction) = if normal.y != 0. {
+            (CollisionAxis::Vertical, correction.y)
+        } else {
+            (CollisionAxis::Horizontal, correction.x)
+        };
+        let details = Some(CollideeDetails { correction });
+        match axis {
+            CollisionAxis::Horizontal => self.horizontal = details,
+            CollisionAxis::Vertical => self.vertical = details,
+        }
+
+        CollisionEvent {
+            entity: entity_a,
+            other: Some(entity_b),
+            axis,
+            penetration: correction.abs(),
+            normal,
+            contact_position,
         }
     }
 }
@@ -159,6 +305,13 @@ pub struct Collider {
     pub hit_box_offset_front: f32,
     pub hit_box_offset_back: f32,
     pub is_collidable: bool,
+    /// Layers this collider belongs to.
+    pub layer: CollisionLayers,
+    /// Layers this collider collides with.
+    pub mask: CollisionLayers,
+    /// Whether the collisions are found along the whole motion of the bounding box during the
+    /// frame rather than at its end, so that fast colliders can't go through thin ones.
+    pub is_continuous: bool,
 }
 
 impl Default for Collider {
@@ -171,6 +324,9 @@ impl Default for Collider {
             hit_box_offset_front: 0.,
             hit_box_offset_back: 0.,
             is_collidable: true,
+            layer: CollisionLayers::TERRAIN,
+            mask: CollisionLayers::ALL,
+            is_continuous: false,
         }
     }
 }
@@ -185,6 +341,9 @@ impl Collider {
             hit_box_offset_front: 0.,
             hit_box_offset_back: 0.,
             is_collidable: true,
+            layer: CollisionLayers::TERRAIN,
+            mask: CollisionLayers::ALL,
+            is_continuous: false,
         }
     }
 
@@ -203,6 +362,37 @@ impl Collider {
         };
     }
 
+    /// Sets the layers this collider belongs to and the layers it collides with.
+    pub fn set_layers(&mut self, layer: CollisionLayers, mask: CollisionLayers) {
+        self.layer = layer;
+        self.mask = mask;
+    }
+
+    /// Returns the lower left and the upper right corners of the area covered by both the
+    /// bounding box and the hit box. For continuous colliders, it also covers the old position
+    /// of the bounding box.
+    pub fn bounds(&self) -> (Vector2<f32>, Vector2<f32>) {
+        let (bbox, hbox) = (&self.bounding_box, &self.hit_box);
+        let mut min = Vector2::new(
+            (bbox.position.x - bbox.half_size.x).min(hbox.position.x - hbox.half_size.x),
+            (bbox.position.y - bbox.half_size.y).min(hbox.position.y - hbox.half_size.y),
+        );
+        let mut max = Vector2::new(
+            (bbox.position.x + bbox.half_size.x).max(hbox.position.x + hbox.half_size.x),
+            (bbox.position.y + bbox.half_size.y).max(hbox.position.y + hbox.half_size.y),
+        );
+        if self.is_continuous {
+            min = min.zip_map(&(bbox.old_position - bbox.half_size), f32::min);
+            max = max.zip_map(&(bbox.old_position + bbox.half_size), f32::max);
+        }
+        (min, max)
+    }
+
+    /// Returns whether this collider has to be stopped by, and notified about, `other`.
+    pub fn can_collide_with(&self, other: &Collider) -> bool {
+        self.mask.intersects(other.layer)
+    }
+
     pub fn is_overlapping_with(&self, other: &Collider, use_hit_box: bool) -> bool {
         let (self_box, other_box) = if use_hit_box {
             (&self.hit_box, &other.hit_box)
@@ -215,3 +405,77 @@ impl Collider {
                 >= (self_box.half_size.y + other_box.half_size.y).abs())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_combines_layer_names() {
+        assert_eq!(
+            CollisionLayers::parse("terrain|player_projectile"),
+            Some(CollisionLayers::TERRAIN | CollisionLayers::PLAYER_PROJECTILE)
+        );
+        assert_eq!(
+            CollisionLayers::parse(" enemy | pickup "),
+            Some(CollisionLayers::ENEMY | CollisionLayers::PICKUP)
+        );
+        assert_eq!(CollisionLayers::parse("none"), Some(CollisionLayers::NONE));
+        assert_eq!(CollisionLayers::parse("all"), Some(CollisionLayers::ALL));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_layers() {
+        assert_eq!(CollisionLayers::parse("terrain|water"), None);
+        assert_eq!(CollisionLayers::parse(""), None);
+        assert_eq!(CollisionLayers::parse("terrain|"), None);
+    }
+
+    fn moving_box(size: f32, from: (f32, f32), to: (f32, f32)) -> GenericBox {
+        let mut generic_box = GenericBox::new(size, size);
+        generic_box.old_position = Vector2::new(from.0, from.1);
+        generic_box.position = Vector2::new(to.0, to.1);
+        generic_box
+    }
+
+    #[test]
+    fn sweep_finds_the_time_of_impact() {
+        let bullet = moving_box(2., (0., 0.), (10., 0.));
+        let wall = moving_box(2., (5., 0.), (5., 0.));
+
+        let (time, normal) = bullet.sweep(&wall).unwrap();
+        assert!((time - 0.3).abs() < std::f32::EPSILON);
+        assert_eq!(normal, Vector2::new(-1., 0.));
+    }
+
+    #[test]
+    fn sweep_accounts_for_both_boxes_moving() {
+        let falling = moving_box(2., (0., 10.), (0., 0.));
+        let rising = moving_box(2., (0., 0.), (0., 4.));
+
+        let (time, normal) = falling.sweep(&rising).unwrap();
+        assert!((time - 8. / 14.).abs() < std::f32::EPSILON);
+        assert_eq!(normal, Vector2::new(0., 1.));
+    }
+
+    #[test]
+    fn sweep_misses_a_box_out_of_the_way() {
+        let wall = moving_box(2., (5., 0.), (5., 0.));
+
+        // Passing above it.
+        assert!(moving_box(2., (0., 3.), (10., 3.)).sweep(&wall).is_none());
+        // Stopping short of it.
+        assert!(moving_box(2., (0., 0.), (2., 0.)).sweep(&wall).is_none());
+        // Moving away from it.
+        assert!(moving_box(2., (2., 0.), (-8., 0.)).sweep(&wall).is_none());
+    }
+
+    #[test]
+    fn sweep_touches_overlapping_boxes_right_away() {
+        let wall = moving_box(2., (5., 0.), (5., 0.));
+
+        let (time, normal) = moving_box(2., (4., 0.), (10., 0.)).sweep(&wall).unwrap();
+        assert_eq!(time, 0.);
+        assert_eq!(normal, Vector2::new(0., 0.));
+    }
+}
@@ -1,7 +1,5 @@
 use amethyst::ecs::{Component, DenseVecStorage};
 
-// Remove this allow annotation when the Up and Down variants are taken in use.
-#[allow(dead_code)]
 #[derive(PartialEq, Clone, Copy)]
 pub enum Directions {
     Right,
@@ -51,4 +49,15 @@ impl Direction {
             Directions::Left
         };
     }
+
+    /// Changes the vertical direction based on the y velocity.
+    pub fn set_y_velocity(&mut self, y_velocity: f32) {
+        self.y = if y_velocity.abs() < std::f32::EPSILON {
+            Directions::Neutral
+        } else if y_velocity > 0. {
+            Directions::Up
+        } else {
+            Directions::Down
+        };
+    }
 }
@@ -0,0 +1,55 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Health {
+    pub max: u32,
+    pub current: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Health { max, current: max }
+    }
+
+    pub fn damage(&mut self, amount: u32) {
+        self.current = self.current.saturating_sub(amount);
+    }
+
+    pub fn heal(&mut self, amount: u32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+}
+
+/// Amount of health taken away from whatever this entity hits.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Damage {
+    pub amount: u32,
+}
+
+impl Damage {
+    pub fn new(amount: u32) -> Self {
+        Damage { amount }
+    }
+}
+
+/// Makes an entity immune to damage for a short time after it gets hurt.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Invulnerability {
+    /// Remaining time in seconds.
+    pub remaining: f32,
+}
+
+impl Invulnerability {
+    pub fn new(duration: f32) -> Self {
+        Invulnerability {
+            remaining: duration,
+        }
+    }
+}
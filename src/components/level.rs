@@ -0,0 +1,17 @@
+use amethyst::ecs::{Component, NullStorage};
+
+/// Marks the entities belonging to the current level.
+/// They are all deleted when the level is unloaded.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Level;
+
+/// Trigger area which ends the current level when the marine enters it.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Exit;
+
+/// Trigger area which moves the respawn point of the marine when the marine enters it.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Checkpoint;
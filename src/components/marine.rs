@@ -1,12 +1,19 @@
-use amethyst::ecs::{Component, DenseVecStorage};
+use std::collections::VecDeque;
+
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Component, DenseVecStorage},
+};
+
+use crate::components::Weapon;
 
 #[derive(Eq, Hash, PartialEq, Clone, Copy)]
 pub enum MarineState {
     Dying,
+    Hurt,
     Idling,
     Jumping,
     Running,
-    Shooting,
 }
 
 impl Default for MarineState {
@@ -15,24 +22,68 @@ impl Default for MarineState {
     }
 }
 
+/// What the marine does with its gun, on top of the movements of its `MarineState`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarineAction {
+    None,
+    /// Just fired, the shooting pose is held for `time_left` more seconds.
+    Shooting {
+        time_left: f32,
+    },
+}
+
+impl Default for MarineAction {
+    fn default() -> Self {
+        MarineAction::None
+    }
+}
+
 #[derive(Component)]
 #[storage(DenseVecStorage)]
 pub struct Marine {
     pub state: MarineState,
-    pub is_shooting: bool,
+    pub action: MarineAction,
+    /// Where the gun points to, a unit vector along one of eight directions.
+    pub aim: Vector2<f32>,
+    /// Whether the shoot key is down.
+    pub is_trigger_held: bool,
+    /// The weapons carried besides the `Weapon` in its hands, in the order they are cycled
+    /// through.
+    pub holstered: VecDeque<Weapon>,
     pub has_shot: bool,
-    pub max_ground_speed: f32,
-    pub max_air_speed: f32,
+    /// Set when the marine gets hit, until it lands back on the ground.
+    pub is_knocked_back: bool,
+    /// Velocity given to the marine by the last hit, applied on the next frame.
+    pub knockback: Option<Vector2<f32>>,
+    /// Time left to jump after walking off a ledge, in seconds.
+    pub coyote_time: f32,
+    /// Time left during which a jump press is still honored, e.g. when it happens just before
+    /// landing, in seconds.
+    pub jump_buffer: f32,
+    /// Whether the jump key is down. A jump is cut short when it is released early.
+    pub is_jump_held: bool,
+}
+
+impl Default for Marine {
+    fn default() -> Self {
+        Marine::new()
+    }
 }
 
 impl Marine {
     pub fn new() -> Self {
         Marine {
             state: MarineState::Idling,
-            is_shooting: false,
+            action: MarineAction::None,
+            aim: Vector2::new(1., 0.),
+            is_trigger_held: false,
+            holstered: VecDeque::new(),
             has_shot: false,
-            max_ground_speed: 6.,
-            max_air_speed: 12.,
+            is_knocked_back: false,
+            knockback: None,
+            coyote_time: 0.,
+            jump_buffer: 0.,
+            is_jump_held: false,
         }
     }
 }
@@ -1,34 +1,59 @@
 mod animation;
+mod behavior;
 mod bullet;
 mod collision;
 mod direction;
 mod explosion;
 mod flier;
+mod health;
+mod level;
 mod marine;
 mod motion;
+mod owner;
 mod parallax;
+mod perception;
+mod pickup;
 mod pincer;
+mod score;
 mod subject;
+mod turret;
+mod weapon;
 
 pub use self::animation::Animation;
 pub use self::animation::AnimationId;
 pub use self::animation::AnimationPrefabData;
+pub use self::behavior::Behavior;
+pub use self::behavior::NodeMemory;
 pub use self::bullet::Bullet;
 pub use self::bullet::BulletImpact;
+pub use self::bullet::Faction;
 pub use self::collision::Boundary;
 pub use self::collision::Collidee;
 pub use self::collision::CollideeDetails;
 pub use self::collision::Collider;
+pub use self::collision::CollisionLayers;
 pub use self::collision::GenericBox;
 pub use self::direction::Direction;
 pub use self::direction::Directions;
 pub use self::explosion::Explosion;
 pub use self::flier::Flier;
-pub use self::flier::FlierAi;
+pub use self::health::Damage;
+pub use self::health::Health;
+pub use self::health::Invulnerability;
+pub use self::level::Checkpoint;
+pub use self::level::Exit;
+pub use self::level::Level;
 pub use self::marine::Marine;
+pub use self::marine::MarineAction;
 pub use self::marine::MarineState;
 pub use self::motion::Motion;
+pub use self::owner::Owner;
 pub use self::parallax::Parallax;
+pub use self::perception::Perception;
+pub use self::pickup::Pickup;
 pub use self::pincer::Pincer;
-pub use self::pincer::PincerAi;
+pub use self::score::Bounty;
+pub use self::score::Score;
 pub use self::subject::Subject;
+pub use self::turret::Turret;
+pub use self::weapon::Weapon;
@@ -12,6 +12,12 @@ pub struct Motion {
     pub has_jumped: bool,
 }
 
+impl Default for Motion {
+    fn default() -> Self {
+        Motion::new()
+    }
+}
+
 impl Motion {
     pub fn new() -> Self {
         Motion {
@@ -20,13 +26,17 @@ impl Motion {
         }
     }
 
+    /// Accelerates for `delta_seconds`, in the direction the entity is facing, while keeping
+    /// the horizontal speed between `min_limit` and `max_limit`.
     pub fn update_velocity(
         &mut self,
         acceleration: Vector2<f32>,
         dir: &Direction,
         min_limit: f32,
         max_limit: f32,
+        delta_seconds: f32,
     ) {
+        let acceleration = acceleration * delta_seconds;
         match dir.x {
             Directions::Right => {
                 self.velocity.x += acceleration.x;
@@ -0,0 +1,14 @@
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
+
+/// The entity which fired a projectile, held responsible for whatever the projectile hits.
+#[derive(Clone, Copy, Component)]
+#[storage(DenseVecStorage)]
+pub struct Owner {
+    pub entity: Entity,
+}
+
+impl Owner {
+    pub fn new(entity: Entity) -> Self {
+        Owner { entity }
+    }
+}
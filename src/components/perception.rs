@@ -0,0 +1,34 @@
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
+
+use crate::resources::PerceptionConfig;
+
+/// What an enemy can perceive of the marine, and the target it's after.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Perception {
+    pub config: PerceptionConfig,
+    /// The entity being tracked, kept until the attention span runs out once it's no longer
+    /// perceived.
+    pub target: Option<Entity>,
+    /// Whether the target is in sight right now.
+    pub sees_target: bool,
+    /// Time left before losing interest in the target, in seconds.
+    pub interest: f32,
+}
+
+impl Perception {
+    pub fn new(config: PerceptionConfig) -> Self {
+        Perception {
+            config,
+            target: None,
+            sees_target: false,
+            interest: 0.,
+        }
+    }
+
+    /// Starts tracking `target`, or keeps tracking it for a full attention span.
+    pub fn alert(&mut self, target: Entity) {
+        self.target = Some(target);
+        self.interest = self.config.attention_span;
+    }
+}
@@ -0,0 +1,16 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// An item lying around in the level, waiting to be collected by the marine.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Pickup {
+    pub kind: String,
+}
+
+impl Pickup {
+    pub fn new(kind: &str) -> Self {
+        Pickup {
+            kind: kind.to_string(),
+        }
+    }
+}
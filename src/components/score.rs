@@ -0,0 +1,22 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Kills credited to an entity and the points they earned it.
+#[derive(Component, Default)]
+#[storage(DenseVecStorage)]
+pub struct Score {
+    pub kills: u32,
+    pub points: u32,
+}
+
+/// Points earned by whoever kills the entity.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Bounty {
+    pub points: u32,
+}
+
+impl Bounty {
+    pub fn new(points: u32) -> Self {
+        Bounty { points }
+    }
+}
@@ -0,0 +1,6 @@
+use amethyst::ecs::{Component, NullStorage};
+
+/// Stationary enemy firing its `Weapon` at the marine whenever it can see it.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Turret;
@@ -0,0 +1,53 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+use crate::resources::{ProjectileDefinition, WeaponDefinition};
+
+/// Weapon in the hands of an entity, as defined in `resources/weapons.ron`, along with its
+/// state.
+#[derive(Clone, Component)]
+#[storage(DenseVecStorage)]
+pub struct Weapon {
+    pub definition: WeaponDefinition,
+    /// The projectile the weapon fires, looked up in the `WeaponList`.
+    pub projectile: ProjectileDefinition,
+    /// Shots left, `None` for the weapons with unlimited ammo.
+    pub ammo: Option<u32>,
+    /// Time left before the weapon can fire again, in seconds.
+    pub cooldown: f32,
+    /// How long the trigger has been held for a charged shot, in seconds.
+    pub charge: f32,
+}
+
+impl Weapon {
+    pub fn new(definition: WeaponDefinition, projectile: ProjectileDefinition) -> Self {
+        Weapon {
+            ammo: definition.ammo_capacity,
+            definition,
+            projectile,
+            cooldown: 0.,
+            charge: 0.,
+        }
+    }
+
+    pub fn has_ammo(&self) -> bool {
+        self.ammo.map_or(true, |ammo| ammo > 0)
+    }
+
+    /// Fills the weapon up with ammo.
+    pub fn refill(&mut self) {
+        self.ammo = self.definition.ammo_capacity;
+    }
+
+    /// Whether the weapon has cooled down since its last shot and has ammo left.
+    pub fn is_ready(&self) -> bool {
+        self.cooldown <= 0. && self.has_ammo()
+    }
+
+    /// Starts the cooldown and uses up one shot.
+    pub fn spend_shot(&mut self) {
+        self.cooldown = self.definition.cooldown;
+        if let Some(ammo) = self.ammo.as_mut() {
+            *ammo = ammo.saturating_sub(1);
+        }
+    }
+}
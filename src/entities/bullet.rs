@@ -1,3 +1,5 @@
+use std::f32::consts::FRAC_PI_4;
+
 use amethyst::{
     assets::{Handle, Prefab},
     core::{
@@ -11,22 +13,40 @@ use amethyst::{
 use crate::{
     components::{
         Animation, AnimationId, AnimationPrefabData, Boundary, Bullet, BulletImpact, Collidee,
-        Collider, Direction, Directions, Motion,
+        Collider, CollisionLayers, Damage, Direction, Directions, Faction, Level, Motion, Owner,
     },
-    resources::Context,
+    resources::{Context, ProjectileDefinition},
 };
 
+/// Describes a projectile being fired.
+pub struct Shot<'a> {
+    /// Where the projectile leaves the gun.
+    pub muzzle: Vector2<f32>,
+    /// Unit vector the projectile flies along.
+    pub heading: Vector2<f32>,
+    pub projectile: &'a ProjectileDefinition,
+    pub faction: Faction,
+    /// The entity firing the projectile.
+    pub owner: Entity,
+}
+
+/// Fires the `shot`'s projectile, which only collides with the other faction.
 pub fn spawn_bullet(
     entities: &Entities,
     sprite_sheet_handle: SpriteSheetHandle,
-    shoot_start_position: f32,
-    marine_dir: &Direction,
-    marine_bottom: f32,
+    shot: Shot,
     lazy_update: &ReadExpect<LazyUpdate>,
     ctx: &Context,
 ) {
+    let Shot {
+        muzzle,
+        heading: aim,
+        projectile,
+        faction,
+        owner,
+    } = shot;
     let bullet_entity: Entity = entities.create();
-    let scale = ctx.scale;
+    let scale = ctx.scale * projectile.scale;
 
     let mut transform = Transform::default();
     transform.set_scale(Vector3::new(scale, scale, scale));
@@ -36,6 +56,7 @@ pub fn spawn_bullet(
         sprite_number: 0,
     };
     let mut motion = Motion::new();
+    motion.velocity = aim * projectile.speed;
 
     let mut direction = Direction::new(
         Directions::Right,
@@ -43,36 +64,56 @@ pub fn spawn_bullet(
         Directions::Neutral,
         Directions::Neutral,
     );
+    // The sprite can only be tilted in eight directions, spread shots are drawn along the
+    // closest one.
+    let angle = (aim.y.atan2(aim.x) / FRAC_PI_4).round() * FRAC_PI_4;
+    direction.set_x_velocity(angle.cos().round());
+    direction.set_y_velocity(angle.sin().round());
 
-    let mut bullet_start_position = 0.;
-    if marine_dir.x == Directions::Right {
-        motion.velocity.x = 20.;
-        direction.x = Directions::Right;
-        bullet_start_position = shoot_start_position + 22.;
-    } else if marine_dir.x == Directions::Left {
-        motion.velocity.x = -20.;
-        direction.x = Directions::Left;
-        bullet_start_position = shoot_start_position - 22.;
+    // The bullet sprite is drawn horizontally, its box follows the sprite once rotated.
+    let (length, thickness) = (projectile.hit_box.0 * scale, projectile.hit_box.1 * scale);
+    let mut collider = Collider::new(
+        aim.x.abs() * length + aim.y.abs() * thickness,
+        aim.y.abs() * length + aim.x.abs() * thickness,
+    );
+    match faction {
+        Faction::Player => collider.set_layers(
+            CollisionLayers::PLAYER_PROJECTILE,
+            CollisionLayers::TERRAIN | CollisionLayers::ENEMY,
+        ),
+        Faction::Enemy => collider.set_layers(
+            CollisionLayers::ENEMY_PROJECTILE,
+            CollisionLayers::TERRAIN | CollisionLayers::PLAYER,
+        ),
     }
-
-    let mut collider = Collider::new(22. * scale, 4. * scale);
+    // Bullets are fast enough to go through thin walls and small enemies in a single frame.
+    collider.is_continuous = true;
+    let bullet_start_position = muzzle + aim * 22.;
     let bbox = &mut collider.bounding_box;
-    bbox.position = Vector2::new(bullet_start_position, marine_bottom + 48.);
+    bbox.position = bullet_start_position;
     bbox.old_position = bbox.position;
 
-    transform.set_translation_x(bullet_start_position);
-    transform.set_translation_y(marine_bottom + 48.);
+    transform.set_translation_x(bullet_start_position.x);
+    transform.set_translation_y(bullet_start_position.y);
     // bullet should be shown only after making sure that there is no collision at the spawn position
     transform.set_translation_z(-60.);
 
     collider.set_hit_box_position(motion.velocity);
 
-    lazy_update.insert(bullet_entity, Bullet::default());
+    lazy_update.insert(bullet_entity, Bullet::new(faction));
+    lazy_update.insert(bullet_entity, Owner::new(owner));
+    lazy_update.insert(bullet_entity, Damage::new(projectile.damage));
+    lazy_update.insert(bullet_entity, Level::default());
     lazy_update.insert(bullet_entity, Named::new("Bullet"));
     lazy_update.insert(bullet_entity, collider);
     lazy_update.insert(
         bullet_entity,
-        Boundary::new(ctx.x_correction, ctx.map_width, 352., 0.),
+        Boundary::new(
+            ctx.x_correction,
+            ctx.x_correction + ctx.map_width,
+            ctx.bg_height * 2. + ctx.y_correction,
+            ctx.y_correction,
+        ),
     );
     lazy_update.insert(bullet_entity, Collidee::default());
     lazy_update.insert(bullet_entity, sprite_render);
@@ -82,12 +123,13 @@ pub fn spawn_bullet(
     lazy_update.insert(bullet_entity, Transparent);
 }
 
+/// Shows the impact of a bullet which was going along `heading`, a unit vector, when it hit
+/// something at `impact_position`.
 pub fn show_bullet_impact(
     entities: &Entities,
     prefab_handle: Handle<Prefab<AnimationPrefabData>>,
-    impact_position: f32,
-    bullet_position_y: f32,
-    bullet_velocity: f32,
+    impact_position: Vector2<f32>,
+    heading: Vector2<f32>,
     lazy_update: &ReadExpect<LazyUpdate>,
     ctx: &Context,
 ) {
@@ -100,22 +142,20 @@ pub fn show_bullet_impact(
         Directions::Neutral,
         Directions::Neutral,
     );
+    direction.set_x_velocity(heading.x);
+    direction.set_y_velocity(heading.y);
 
-    let impact_position_x = if bullet_velocity > 0. {
-        direction.x = Directions::Right;
-        scale.mul_add(-8., impact_position)
-    } else {
-        direction.x = Directions::Left;
-        scale.mul_add(8., impact_position)
-    };
+    // Drawn a bit in front of whatever was hit.
+    let position = impact_position - heading * 8. * scale;
 
     let mut transform = Transform::default();
     transform.set_scale(Vector3::new(scale, scale, scale));
-    transform.set_translation_x(impact_position_x);
-    transform.set_translation_y(bullet_position_y);
+    transform.set_translation_x(position.x);
+    transform.set_translation_y(position.y);
     transform.set_translation_z(-10.);
 
     lazy_update.insert(bullet_impact_entity, BulletImpact::default());
+    lazy_update.insert(bullet_impact_entity, Level::default());
     lazy_update.insert(
         bullet_impact_entity,
         Animation::new(AnimationId::BulletImpact, vec![AnimationId::BulletImpact]),
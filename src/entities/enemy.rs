@@ -0,0 +1,29 @@
+use amethyst::core::math::Vector2;
+
+use crate::components::{Boundary, CollisionLayers, Directions};
+
+/// Describes an enemy placed in the map.
+pub struct EnemySpawn {
+    pub position: Vector2<f32>,
+    pub facing: Directions,
+    /// Area the enemy patrols.
+    pub boundary: Boundary,
+    pub hit_points: u32,
+    /// Damage dealt to the marine on contact.
+    pub damage: u32,
+    /// Points earned by killing the enemy.
+    pub points: u32,
+    /// Name of the behavior tree the enemy acts out.
+    pub behavior: String,
+}
+
+/// Returns the layers an enemy collides with. Unless they pass through each other, the enemies
+/// bump into one another.
+pub fn enemy_collision_mask(passes_through_enemies: bool) -> CollisionLayers {
+    let mask = CollisionLayers::TERRAIN | CollisionLayers::PLAYER;
+    if passes_through_enemies {
+        mask
+    } else {
+        mask | CollisionLayers::ENEMY
+    }
+}
@@ -0,0 +1,29 @@
+use amethyst::{
+    core::{math::Vector2, Transform, WithNamed},
+    ecs::{prelude::World, Entity},
+    prelude::{Builder, WorldExt},
+};
+
+use crate::components::{Collider, CollisionLayers, Exit, Level};
+
+/// Creates an invisible exit trigger covering the given area.
+pub fn load_exit(world: &mut World, position: Vector2<f32>, width: f32, height: f32) -> Entity {
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(position.x, position.y, 0.);
+
+    let mut collider = Collider::new(width, height);
+    collider.set_layers(CollisionLayers::TRIGGER, CollisionLayers::PLAYER);
+    let bbox = &mut collider.bounding_box;
+    bbox.position = position;
+    bbox.old_position = bbox.position;
+    collider.hit_box = collider.bounding_box.clone();
+
+    world
+        .create_entity()
+        .with(Exit::default())
+        .with(Level::default())
+        .named("Exit")
+        .with(collider)
+        .with(transform)
+        .build()
+}
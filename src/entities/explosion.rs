@@ -6,7 +6,7 @@ use amethyst::{
 };
 
 use crate::{
-    components::{Animation, AnimationId, AnimationPrefabData, Explosion},
+    components::{Animation, AnimationId, AnimationPrefabData, Explosion, Level},
     resources::Context,
 };
 
@@ -26,6 +26,7 @@ pub fn show_explosion(
     transform.set_translation_xyz(transform_x, scale.mul_add(32. - 15., transform_y), 0.);
 
     lazy_update.insert(exposion_entity, Explosion::default());
+    lazy_update.insert(exposion_entity, Level::default());
     lazy_update.insert(
         exposion_entity,
         Animation::new(AnimationId::Explode, vec![AnimationId::Explode]),
@@ -1,59 +1,74 @@
 use amethyst::{
     assets::{Handle, Prefab},
-    core::{
-        math::{Vector2, Vector3},
-        Transform, WithNamed,
-    },
-    ecs::prelude::World,
+    core::{math::Vector3, Transform, WithNamed},
+    ecs::{prelude::World, Entity},
     prelude::{Builder, WorldExt},
     renderer::{palette::Srgba, resources::Tint, transparent::Transparent},
 };
 
 use crate::{
     components::{
-        Animation, AnimationId, AnimationPrefabData, Boundary, Collidee, Collider, Direction,
-        Directions, Flier, GenericBox, Motion,
+        Animation, AnimationId, AnimationPrefabData, Behavior, Bounty, Collidee, Collider,
+        CollisionLayers, Damage, Direction, Directions, Flier, Health, Level, Motion, Perception,
     },
-    resources::Context,
+    entities::{enemy_collision_mask, EnemySpawn},
+    resources::{AssetType, Context, GameplayConfig},
 };
 
-pub fn load_flier(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>>, ctx: &Context) {
+pub fn load_flier(
+    world: &mut World,
+    prefab: Handle<Prefab<AnimationPrefabData>>,
+    spawn: EnemySpawn,
+    ctx: &Context,
+) -> Entity {
     // wing offset
     let flier_sprite_x_offset = 22.;
     // reduce the width of the flier to compensate of the extra width of the wings
     let flier_width = 54. - flier_sprite_x_offset;
     let flier_height = 64.;
 
-    let flier_start_x_pos = 1800.;
-    let flier_start_y_pos = 156.;
     let mut transform = Transform::default();
     let scale = ctx.scale;
     println!("load_flier: scale = {}", scale);
     transform.set_scale(Vector3::new(scale, scale, scale));
 
+    let (speed, perception, mask) = {
+        let config = world.read_resource::<GameplayConfig>();
+        (
+            config.flier.speed,
+            Perception::new(config.flier.perception),
+            enemy_collision_mask(config.flier.pass_through_enemies),
+        )
+    };
+
     let mut collider = Collider::new(flier_width * scale, flier_height * scale);
+    collider.set_layers(CollisionLayers::ENEMY, mask);
 
     // adjust the x offset to compensate for the reduction of width
     collider.hit_box_offset.x = flier_sprite_x_offset;
 
     let bbox = &mut collider.bounding_box;
-    bbox.position = Vector2::new(flier_start_x_pos, flier_start_y_pos);
+    bbox.position = spawn.position;
     bbox.old_position = bbox.position;
 
-    transform.set_translation_x(flier_start_x_pos);
-    transform.set_translation_y(flier_start_y_pos);
+    transform.set_translation_x(spawn.position.x);
+    transform.set_translation_y(spawn.position.y);
 
     let mut motion = Motion::new();
-    // Make the flier a teeny bit faster than the pincer since its easier to dodge
-    motion.velocity.x = -4.;
+    motion.velocity.x = if spawn.facing == Directions::Right {
+        speed
+    } else {
+        -speed
+    };
     collider.set_hit_box_position(motion.velocity);
 
-    let direction = Direction::new(
+    let mut direction = Direction::new(
         Directions::Left,
         Directions::Neutral,
         Directions::Left,
         Directions::Neutral,
     );
+    direction.set_x_velocity(motion.velocity.x);
 
     // White shows the sprite as normal.
     // You can change the color at any point to modify the sprite's tint.
@@ -61,11 +76,21 @@ pub fn load_flier(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>>
 
     world
         .create_entity()
-        .with(Flier::new())
+        .with(Flier)
+        .with(Behavior::new(
+            spawn.behavior,
+            AssetType::Flier,
+            spawn.position,
+        ))
+        .with(Health::new(spawn.hit_points))
+        .with(Damage::new(spawn.damage))
+        .with(Bounty::new(spawn.points))
+        .with(perception)
+        .with(Level::default())
         .named("Flier")
         .with(collider)
         .with(tint)
-        .with(Boundary::new(1800., 2575., 352., 0.))
+        .with(spawn.boundary)
         .with(Collidee::default())
         .with(transform)
         .with(motion)
@@ -76,5 +101,5 @@ pub fn load_flier(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>>
         .with(prefab)
         .with(direction)
         .with(Transparent) // Necessary for ordered layering
-        .build();
+        .build()
 }
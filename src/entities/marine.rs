@@ -4,40 +4,73 @@ use amethyst::{
         math::{Vector2, Vector3},
         Transform, WithNamed,
     },
-    ecs::prelude::World,
+    ecs::{prelude::World, Entity},
     prelude::{Builder, WorldExt},
-    renderer::transparent::Transparent,
+    renderer::{palette::Srgba, resources::Tint, transparent::Transparent},
 };
 
 use crate::{
     components::{
-        Animation, AnimationId, AnimationPrefabData, Boundary, Collidee, Collider, Direction,
-        Directions, Marine, Motion,
+        Animation, AnimationId, AnimationPrefabData, Boundary, Collidee, Collider, CollisionLayers,
+        Direction, Directions, Health, Level, Marine, Motion, Score, Weapon,
     },
-    resources::Context,
+    resources::{Context, WeaponList},
 };
 
-pub fn load_marine(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>>, ctx: &Context) {
+pub fn load_marine(
+    world: &mut World,
+    prefab: Handle<Prefab<AnimationPrefabData>>,
+    position: Vector2<f32>,
+    hit_points: u32,
+    ctx: &Context,
+) -> Entity {
     let scale = ctx.scale;
     let mut transform = Transform::default();
     transform.set_scale(Vector3::new(scale, scale, scale));
-    transform.set_translation_x(384.);
-    transform.set_translation_y(176.);
+    transform.set_translation_x(position.x);
+    transform.set_translation_y(position.y);
 
     let mut collider = Collider::new(32. * scale, 36. * scale);
+    collider.set_layers(
+        CollisionLayers::PLAYER,
+        CollisionLayers::TERRAIN | CollisionLayers::ENEMY | CollisionLayers::ENEMY_PROJECTILE,
+    );
     let bbox = &mut collider.bounding_box;
-    bbox.position = Vector2::new(384., 176.);
+    bbox.position = position;
     bbox.old_position = bbox.position;
 
     let motion = Motion::new();
     collider.set_hit_box_position(motion.velocity);
 
-    world
+    // The marine starts with the first weapon of the list in its hands and the others holstered.
+    let mut marine = Marine::new();
+    let weapon = {
+        let weapon_list = world.read_resource::<WeaponList>();
+        let mut weapons = weapon_list.weapons.iter().map(|definition| {
+            Weapon::new(
+                definition.clone(),
+                weapon_list.projectile(definition).clone(),
+            )
+        });
+        let weapon = weapons.next();
+        marine.holstered = weapons.collect();
+        weapon
+    };
+
+    let mut builder = world
         .create_entity()
-        .with(Marine::new())
+        .with(marine)
+        .with(Health::new(hit_points))
+        .with(Score::default())
+        .with(Level::default())
         .named("Marine")
         .with(collider)
-        .with(Boundary::new(ctx.x_correction, ctx.map_width, 352., 0.))
+        .with(Boundary::new(
+            ctx.x_correction,
+            ctx.x_correction + ctx.map_width,
+            ctx.bg_height * 2. + ctx.y_correction,
+            ctx.y_correction,
+        ))
         .with(Collidee::default())
         .with(transform)
         .with(motion)
@@ -49,6 +82,8 @@ pub fn load_marine(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>
                 AnimationId::Jump,
                 AnimationId::Move,
                 AnimationId::Shoot,
+                AnimationId::RunShoot,
+                AnimationId::JumpShoot,
             ],
         ))
         .with(prefab)
@@ -58,6 +93,11 @@ pub fn load_marine(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>
             Directions::Right,
             Directions::Neutral,
         ))
-        .with(Transparent) // Necessary for ordered layering
-        .build();
+        // Blinks while the marine is invulnerable.
+        .with(Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)))
+        .with(Transparent); // Necessary for ordered layering
+    if let Some(weapon) = weapon {
+        builder = builder.with(weapon);
+    }
+    builder.build()
 }
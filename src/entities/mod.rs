@@ -1,16 +1,28 @@
 mod bullet;
 mod camera;
 mod camera_subject;
+mod checkpoint;
+mod enemy;
+mod exit;
 mod explosion;
 mod flier;
 mod marine;
+mod pickup;
 mod pincer;
+mod turret;
 
 pub use self::bullet::show_bullet_impact;
 pub use self::bullet::spawn_bullet;
+pub use self::bullet::Shot;
 pub use self::camera::load_camera;
 pub use self::camera_subject::load_camera_subject;
+pub use self::checkpoint::load_checkpoint;
+pub use self::enemy::enemy_collision_mask;
+pub use self::enemy::EnemySpawn;
+pub use self::exit::load_exit;
 pub use self::explosion::show_explosion;
 pub use self::flier::load_flier;
 pub use self::marine::load_marine;
+pub use self::pickup::load_pickup;
 pub use self::pincer::load_pincer;
+pub use self::turret::load_turret;
@@ -0,0 +1,52 @@
+use amethyst::{
+    core::{
+        math::{Vector2, Vector3},
+        Transform, WithNamed,
+    },
+    ecs::{prelude::World, Entity},
+    prelude::{Builder, WorldExt},
+    renderer::{sprite::SpriteSheetHandle, transparent::Transparent, SpriteRender},
+};
+
+use crate::{
+    components::{Collider, CollisionLayers, Level, Pickup},
+    resources::Context,
+};
+
+pub fn load_pickup(
+    world: &mut World,
+    sprite_sheet_handle: SpriteSheetHandle,
+    position: Vector2<f32>,
+    kind: &str,
+    sprite_number: usize,
+    ctx: &Context,
+) -> Entity {
+    let scale = ctx.scale;
+    let mut transform = Transform::default();
+    transform.set_scale(Vector3::new(scale, scale, scale));
+    transform.set_translation_xyz(position.x, position.y, -5.);
+
+    let mut collider = Collider::new(16. * scale, 16. * scale);
+    collider.set_layers(CollisionLayers::PICKUP, CollisionLayers::PLAYER);
+    let bbox = &mut collider.bounding_box;
+    bbox.position = position;
+    bbox.old_position = bbox.position;
+    collider.hit_box = collider.bounding_box.clone();
+
+    let sprite_render = SpriteRender {
+        sprite_sheet: sprite_sheet_handle,
+        sprite_number,
+    };
+
+    // Pickups have no `Motion`, so they never block any other entity.
+    world
+        .create_entity()
+        .with(Pickup::new(kind))
+        .with(Level::default())
+        .named("Pickup")
+        .with(collider)
+        .with(transform)
+        .with(sprite_render)
+        .with(Transparent)
+        .build()
+}
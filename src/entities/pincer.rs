@@ -1,49 +1,68 @@
 use amethyst::{
     assets::{Handle, Prefab},
-    core::{
-        math::{Vector2, Vector3},
-        Transform, WithNamed,
-    },
-    ecs::prelude::World,
+    core::{math::Vector3, Transform, WithNamed},
+    ecs::{prelude::World, Entity},
     prelude::{Builder, WorldExt},
     renderer::{palette::Srgba, resources::Tint, transparent::Transparent},
 };
 
 use crate::{
     components::{
-        Animation, AnimationId, AnimationPrefabData, Boundary, Collidee, Collider, Direction,
-        Directions, GenericBox, Motion, Pincer,
+        Animation, AnimationId, AnimationPrefabData, Behavior, Bounty, Collidee, Collider,
+        CollisionLayers, Damage, Direction, Directions, GenericBox, Health, Level, Motion,
+        Perception, Pincer,
     },
-    resources::Context,
+    entities::{enemy_collision_mask, EnemySpawn},
+    resources::{AssetType, Context, GameplayConfig},
 };
 
-pub fn load_pincer(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>>, ctx: &Context) {
+pub fn load_pincer(
+    world: &mut World,
+    prefab: Handle<Prefab<AnimationPrefabData>>,
+    spawn: EnemySpawn,
+    ctx: &Context,
+) -> Entity {
     let mut transform = Transform::default();
     let scale = ctx.scale;
     transform.set_scale(Vector3::new(scale, scale, scale));
 
+    let (speed, perception, mask) = {
+        let config = world.read_resource::<GameplayConfig>();
+        (
+            config.pincer.speed,
+            Perception::new(config.pincer.perception),
+            enemy_collision_mask(config.pincer.pass_through_enemies),
+        )
+    };
+
     let mut collider = Collider::new(40. * scale, 30. * scale);
+    collider.set_layers(CollisionLayers::ENEMY, mask);
 
     collider.hit_box = GenericBox::new(40. * scale - 30., 30. * scale);
     collider.hit_box_offset.x = 15.;
 
     let bbox = &mut collider.bounding_box;
-    bbox.position = Vector2::new(1040., 16.);
+    bbox.position = spawn.position;
     bbox.old_position = bbox.position;
 
-    transform.set_translation_x(1040.);
-    transform.set_translation_y(16.);
+    transform.set_translation_x(spawn.position.x);
+    transform.set_translation_y(spawn.position.y);
 
     let mut motion = Motion::new();
-    motion.velocity.x = -3.;
+    motion.velocity.x = if spawn.facing == Directions::Right {
+        speed
+    } else {
+        -speed
+    };
     collider.set_hit_box_position(motion.velocity);
 
-    let direction = Direction::new(
+    let mut direction = Direction::new(
         Directions::Left,
         Directions::Neutral,
         Directions::Left,
         Directions::Neutral,
     );
+    direction.set_x_velocity(motion.velocity.x);
 
     // White shows the sprite as normal.
     // You can change the color at any point to modify the sprite's tint.
@@ -51,11 +70,21 @@ pub fn load_pincer(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>
 
     world
         .create_entity()
-        .with(Pincer::new())
+        .with(Pincer)
+        .with(Behavior::new(
+            spawn.behavior,
+            AssetType::Pincer,
+            spawn.position,
+        ))
+        .with(Health::new(spawn.hit_points))
+        .with(Damage::new(spawn.damage))
+        .with(Bounty::new(spawn.points))
+        .with(perception)
+        .with(Level::default())
         .named("Pincer")
         .with(collider)
         .with(tint)
-        .with(Boundary::new(800., 1832., 352., 0.))
+        .with(spawn.boundary)
         .with(Collidee::default())
         .with(transform)
         .with(motion)
@@ -66,5 +95,5 @@ pub fn load_pincer(world: &mut World, prefab: Handle<Prefab<AnimationPrefabData>
         .with(prefab)
         .with(direction)
         .with(Transparent) // Necessary for ordered layering
-        .build();
+        .build()
 }
@@ -0,0 +1,92 @@
+use amethyst::{
+    assets::{Handle, Prefab},
+    core::{math::Vector3, Transform, WithNamed},
+    ecs::{prelude::World, Entity},
+    prelude::{Builder, WorldExt},
+    renderer::{palette::Srgba, resources::Tint, transparent::Transparent},
+};
+
+use crate::{
+    components::{
+        Animation, AnimationId, AnimationPrefabData, Behavior, Bounty, Collidee, Collider,
+        CollisionLayers, Damage, Direction, Directions, Health, Level, Motion, Perception, Score,
+        Turret, Weapon,
+    },
+    entities::{enemy_collision_mask, EnemySpawn},
+    resources::{AssetType, Context, GameplayConfig, WeaponList},
+};
+
+pub fn load_turret(
+    world: &mut World,
+    prefab: Handle<Prefab<AnimationPrefabData>>,
+    spawn: EnemySpawn,
+    ctx: &Context,
+) -> Entity {
+    let mut transform = Transform::default();
+    let scale = ctx.scale;
+    transform.set_scale(Vector3::new(scale, scale, scale));
+
+    let (perception, mask) = {
+        let config = world.read_resource::<GameplayConfig>();
+        (
+            Perception::new(config.turret.perception),
+            enemy_collision_mask(config.turret.pass_through_enemies),
+        )
+    };
+
+    let mut collider = Collider::new(40. * scale, 30. * scale);
+    collider.set_layers(CollisionLayers::ENEMY, mask);
+
+    let bbox = &mut collider.bounding_box;
+    bbox.position = spawn.position;
+    bbox.old_position = bbox.position;
+
+    transform.set_translation_x(spawn.position.x);
+    transform.set_translation_y(spawn.position.y);
+
+    // The turret never moves, but it still needs a motion to be found by the collisions.
+    let motion = Motion::new();
+    collider.set_hit_box_position(motion.velocity);
+
+    let direction = Direction::new(
+        Directions::Left,
+        Directions::Neutral,
+        spawn.facing,
+        Directions::Neutral,
+    );
+
+    let weapon = {
+        let weapon_list = world.read_resource::<WeaponList>();
+        Weapon::new(
+            weapon_list.turret.clone(),
+            weapon_list.projectile(&weapon_list.turret).clone(),
+        )
+    };
+
+    world
+        .create_entity()
+        .with(Turret)
+        .with(Behavior::new(
+            spawn.behavior,
+            AssetType::Turret,
+            spawn.position,
+        ))
+        .with(weapon)
+        .with(perception)
+        .with(Health::new(spawn.hit_points))
+        .with(Damage::new(spawn.damage))
+        .with(Bounty::new(spawn.points))
+        .with(Score::default())
+        .with(Level::default())
+        .named("Turret")
+        .with(collider)
+        .with(Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)))
+        .with(Collidee::default())
+        .with(transform)
+        .with(motion)
+        .with(Animation::new(AnimationId::Idle, vec![AnimationId::Idle]))
+        .with(prefab)
+        .with(direction)
+        .with(Transparent) // Necessary for ordered layering
+        .build()
+}
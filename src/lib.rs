@@ -0,0 +1,13 @@
+// Nearly every Amethyst system triggers this warning, better ignore it:
+#![allow(clippy::type_complexity)]
+extern crate amethyst;
+
+#[macro_use]
+extern crate log;
+extern crate specs_derive;
+
+pub mod components;
+pub mod entities;
+pub mod resources;
+pub mod states;
+pub mod systems;
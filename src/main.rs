@@ -1,15 +1,10 @@
-// Nearly every Amethyst system triggers this warning, better ignore it:
-#![allow(clippy::type_complexity)]
 extern crate amethyst;
 
-#[macro_use]
-extern crate log;
-extern crate specs_derive;
-
 use amethyst::{
     animation::AnimationBundle,
     assets::{PrefabLoaderSystemDesc, Processor},
-    core::transform::TransformBundle,
+    config::Config,
+    core::{transform::TransformBundle, SystemExt},
     input::{InputBundle, StringBindings},
     renderer::{
         plugins::{RenderFlat2D, RenderToWindow},
@@ -22,22 +17,24 @@ use amethyst::{
     Application, GameDataBuilder,
 };
 
-mod components;
-mod entities;
-mod resources;
-mod states;
-mod systems;
-
-use components::{AnimationId, AnimationPrefabData};
-use resources::Map;
-use systems::*;
+use space_menace::{
+    components::{AnimationId, AnimationPrefabData},
+    resources::{BehaviorLibrary, Campaign, GameplayConfig, Map, RunState, WeaponList},
+    states,
+    systems::*,
+};
 
 fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
 
     let root = application_root_dir()?;
     let display_config_path = root.join("resources/display_config.ron");
+    let campaign = Campaign::load(root.join("resources/campaign.ron"))?;
+    let gameplay_config_path = root.join("resources/gameplay.ron");
+    let gameplay_config = GameplayConfig::load(&gameplay_config_path)?;
+    let weapon_list = WeaponList::load(root.join("resources/weapons.ron"))?;
     let assets_path = root.join("assets");
+    let behavior_library = BehaviorLibrary::load(&assets_path.join("behaviors"))?;
     let input_bundle = InputBundle::<StringBindings>::new()
         .with_bindings_from_file(root.join("resources/bindings_config.ron"))?;
 
@@ -59,79 +56,73 @@ fn main() -> amethyst::Result<()> {
         .with(Processor::<Map>::new(), "map_processor", &[])
         .with(UiFpsSystem::default(), "ui_fps_system", &[])
         .with(UiPlayerSystem::default(), "ui_player_system", &[])
-        .with(MarineInputSystem, "marine_input_system", &[])
         .with(
-            MarineKinematicsSystem,
-            "marine_kinematics_system",
-            &["marine_input_system"],
+            GameplayConfigReloadSystem::new(gameplay_config_path),
+            "gameplay_config_reload_system",
+            &[],
         )
         .with(
-            KinematicsSystem,
-            "kinematics_system",
-            &["marine_kinematics_system"],
+            InterpolationSystem.pausable(RunState::Running),
+            "interpolation_system",
+            &[],
         )
-        .with(AttackSystem, "attack_system", &["kinematics_system"])
-        .with(PincerAiSystem, "pincer_ai_system", &[])
-        .with(FlierAiSystem, "flier_ai_system", &[])
-        .with(CollisionSystem, "collision_system", &["attack_system"])
         .with(
-            BulletCollisionSystem,
-            "bullet_collision_system",
-            &["collision_system"],
+            BulletTransformationSystem.pausable(RunState::Running),
+            "bullet_transformation_system",
+            &["interpolation_system"],
         )
         .with(
-            PincerCollisionSystem,
-            "pincer_collision_system",
-            &["collision_system"],
+            BulletImpactAnimationSystem.pausable(RunState::Running),
+            "bullet_impact_animation_system",
+            &["bullet_transformation_system"],
         )
         .with(
-            FlierCollisionSystem,
-            "flier_collision_system",
-            &["collision_system"],
+            PincerAnimationSystem.pausable(RunState::Running),
+            "pincer_animation_system",
+            &["interpolation_system"],
         )
         .with(
-            MarineCollisionSystem,
-            "marine_collision_system",
-            &["collision_system"],
+            FlierAnimationSystem.pausable(RunState::Running),
+            "flier_animation_system",
+            &["interpolation_system"],
         )
         .with(
-            TransformationSystem,
-            "transformation_system",
-            &[
-                "flier_collision_system",
-                "pincer_collision_system",
-                "bullet_collision_system",
-            ],
+            ExplosionAnimationSystem.pausable(RunState::Running),
+            "explosion_animation_system",
+            &[],
         )
         .with(
-            BulletTransformationSystem,
-            "bullet_transformation_system",
-            &["transformation_system"],
+            InvulnerabilitySystem.pausable(RunState::Running),
+            "invulnerability_system",
+            &[],
         )
         .with(
-            BulletImpactAnimationSystem,
-            "bullet_impact_animation_system",
-            &["bullet_transformation_system"],
+            ParallaxSystem::default().pausable(RunState::Running),
+            "parallax_system",
+            &["interpolation_system"],
         )
         .with(
-            PincerAnimationSystem,
-            "pincer_animation_system",
-            &["transformation_system"],
+            ExitSystem.pausable(RunState::Running),
+            "exit_system",
+            &["interpolation_system"],
         )
         .with(
-            FlierAnimationSystem,
-            "flier_animation_system",
-            &["transformation_system"],
+            CheckpointSystem.pausable(RunState::Running),
+            "checkpoint_system",
+            &["interpolation_system"],
+        )
+        .with(
+            PickupSystem.pausable(RunState::Running),
+            "pickup_system",
+            &["interpolation_system"],
         )
-        .with(ExplosionAnimationSystem, "explosion_animation_system", &[])
-        .with(ParallaxSystem, "parallax_system", &[])
         .with(
-            MarineAnimationSystem,
+            MarineAnimationSystem.pausable(RunState::Running),
             "marine_animation_system",
-            &["transformation_system"],
+            &["interpolation_system"],
         )
         .with(
-            AnimationControlSystem,
+            AnimationControlSystem.pausable(RunState::Running),
             "animation_control_system",
             &[
                 "marine_animation_system",
@@ -141,19 +132,20 @@ fn main() -> amethyst::Result<()> {
             ],
         )
         .with(
-            DirectionSystem,
+            DirectionSystem.pausable(RunState::Running),
             "direction_system",
-            &["transformation_system"],
+            &["interpolation_system"],
         )
         .with(
-            MarineDeathSystem,
+            MarineDeathSystem.pausable(RunState::Running),
             "marine_death_system",
-            &["transformation_system"],
+            &["interpolation_system"],
         )
+        .with(ScreenResizeSystem, "screen_resize_system", &[])
         .with(
             CameraTransformationSystem,
             "camera_transformation_system",
-            &["transformation_system"],
+            &["interpolation_system", "screen_resize_system"],
         )
         .with_bundle(
             RenderingBundle::<DefaultBackend>::new()
@@ -166,8 +158,12 @@ fn main() -> amethyst::Result<()> {
                 .with_plugin(RenderUi::default()),
         )?;
 
-    let mut game =
-        Application::build(assets_path, states::LoadState::default())?.build(game_data)?;
+    let mut game = Application::build(assets_path, states::LoadState::default())?
+        .with_resource(campaign)
+        .with_resource(gameplay_config)
+        .with_resource(weapon_list)
+        .with_resource(behavior_library)
+        .build(game_data)?;
 
     game.run();
 
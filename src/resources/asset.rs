@@ -11,9 +11,11 @@ use amethyst::{
     },
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::components::AnimationPrefabData;
 
-#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum AssetType {
     Background,
     Bullet,
@@ -21,9 +23,11 @@ pub enum AssetType {
     Marine,
     Pincer,
     Flier,
+    Pickup,
     Platform,
     SmallExplosion,
     Truss,
+    Turret,
 }
 
 #[derive(Default)]
@@ -74,16 +78,22 @@ pub fn load_assets(world: &mut World, asset_type_list: Vec<AssetType>) -> Progre
             AssetType::Marine => ("", "prefabs/marine.ron"),
             AssetType::Pincer => ("", "prefabs/pincer.ron"),
             AssetType::Flier => ("", "prefabs/flier.ron"),
+            AssetType::Pickup => ("textures/pickup.png", "prefabs/pickup.ron"),
             AssetType::Platform => ("textures/platform.png", "prefabs/platform.ron"),
             AssetType::SmallExplosion => (
                 "textures/small_explosion.png",
                 "prefabs/small_explosion.ron",
             ),
             AssetType::Truss => ("textures/truss.png", "prefabs/truss.ron"),
+            AssetType::Turret => ("", "prefabs/turret.ron"),
         };
 
         match asset_type {
-            AssetType::Background | AssetType::Bullet | AssetType::Platform | AssetType::Truss => {
+            AssetType::Background
+            | AssetType::Bullet
+            | AssetType::Pickup
+            | AssetType::Platform
+            | AssetType::Truss => {
                 let sprite_sheet_handle =
                     get_sprite_sheet_handle(world, texture_path, ron_path, &mut progress_counter);
                 sprite_sheet_list.insert(asset_type, sprite_sheet_handle);
@@ -92,7 +102,8 @@ pub fn load_assets(world: &mut World, asset_type_list: Vec<AssetType>) -> Progre
             | AssetType::Marine
             | AssetType::Pincer
             | AssetType::Flier
-            | AssetType::SmallExplosion => {
+            | AssetType::SmallExplosion
+            | AssetType::Turret => {
                 let prefab_handle =
                     get_animation_prefab_handle(world, ron_path, &mut progress_counter);
                 prefab_list.insert(asset_type, prefab_handle);
@@ -0,0 +1,150 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use amethyst::config::Config;
+use serde::{Deserialize, Serialize};
+
+/// The behavior trees loaded from the `.ron` files of `assets/behaviors`, by file name without
+/// its extension.
+pub struct BehaviorLibrary {
+    trees: HashMap<String, BehaviorTree>,
+}
+
+impl BehaviorLibrary {
+    pub fn load(directory: &Path) -> amethyst::Result<Self> {
+        let mut trees = HashMap::new();
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != "ron")
+            {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                trees.insert(name.to_string(), BehaviorTree::load(&path)?);
+            }
+        }
+        Ok(BehaviorLibrary { trees })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&BehaviorTree> {
+        self.trees.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.trees.contains_key(name)
+    }
+}
+
+/// How an enemy behaves, ticked from its `root` at every physics step.
+///
+/// Speeds are in world units per second and accelerations in world units per second squared,
+/// like in the `GameplayConfig`, which holds the speed and the senses of each enemy kind.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BehaviorTree {
+    /// Pulls the enemy down, zero for the ones which fly or never move.
+    #[serde(default)]
+    pub gravity: f32,
+    /// Keeps a flying enemy bobbing around the altitude it spawned at, unless an action moves
+    /// it vertically.
+    #[serde(default)]
+    pub hover: Option<Hover>,
+    pub root: BehaviorNode,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Hover {
+    /// Height of the waves, from their middle to their crest.
+    pub amplitude: f32,
+    /// Number of waves per second.
+    pub frequency: f32,
+    /// Fastest vertical speed while following the waves.
+    pub speed: f32,
+}
+
+/// Ticking a node either succeeds, fails, or keeps running until a later step.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Success,
+    Failure,
+    Running,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum BehaviorNode {
+    /// Ticks its children in order, until one of them doesn't succeed. Everything is checked
+    /// again at each step.
+    Sequence(Vec<BehaviorNode>),
+    /// Ticks its children in order like a `Sequence`, but resumes from the running one at the
+    /// next step, without checking the ones before again.
+    Steps(Vec<BehaviorNode>),
+    /// Ticks its children in order, until one of them doesn't fail.
+    Selector(Vec<BehaviorNode>),
+    /// Succeeds when its child fails, and the other way around.
+    Not(Box<BehaviorNode>),
+    /// Fails for this many seconds after its child succeeded.
+    Cooldown(f32, Box<BehaviorNode>),
+    Condition(Condition),
+    Action(Action),
+}
+
+impl BehaviorNode {
+    /// Returns the number of nodes of the subtree, this one included. The nodes are numbered
+    /// depth first, so that the running ones can remember where they're at between two steps.
+    pub fn size(&self) -> usize {
+        1 + match self {
+            BehaviorNode::Sequence(children)
+            | BehaviorNode::Steps(children)
+            | BehaviorNode::Selector(children) => children.iter().map(BehaviorNode::size).sum(),
+            BehaviorNode::Not(child) | BehaviorNode::Cooldown(_, child) => child.size(),
+            BehaviorNode::Condition(_) | BehaviorNode::Action(_) => 0,
+        }
+    }
+}
+
+/// Succeeds when it holds, fails otherwise. The ones about the target fail when the enemy's
+/// `Perception` doesn't track any.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum Condition {
+    HasTarget,
+    TargetInSight,
+    /// The target is closer than this distance.
+    TargetWithin(f32),
+    /// The target is closer than this distance along the x axis, whatever its altitude.
+    TargetHorizontallyWithin(f32),
+    TargetAbove,
+    TargetBelow,
+    /// The target stands on the ground, rather than jumping or falling.
+    TargetGrounded,
+    /// The enemy has less than this fraction of its hit points left.
+    HealthBelow(f32),
+    OnGround,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum Action {
+    /// Goes back and forth between the sides of the patrolled area. Keeps running.
+    Patrol,
+    /// Heads toward the target. Keeps running.
+    Chase,
+    /// Follows the target across the platforms, walking off edges and jumping along the
+    /// shortest way the `NavGraph` finds, within the patrolled area. Keeps running, and fails
+    /// when there's no way to the target or the enemy doesn't fall.
+    Pursue,
+    /// Runs away from the target, and stops once cornered. Keeps running.
+    Flee,
+    /// Stands still for this many seconds, then succeeds.
+    Wait(f32),
+    /// Turns toward the target.
+    Face,
+    /// Turns toward the target and fires the enemy's weapon at it. Fails when the target isn't
+    /// in sight or the weapon isn't ready.
+    Shoot,
+    /// Jumps with this vertical velocity. Fails unless the enemy stands on the ground.
+    Jump(f32),
+    /// Dives at this speed toward where the target was when the swoop began, until it gets down
+    /// to the target's altitude or touches the ground.
+    Swoop(f32),
+    /// Climbs back at this speed to the altitude the enemy spawned at.
+    Climb(f32),
+}
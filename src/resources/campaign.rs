@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use amethyst::config::Config;
+use serde::{Deserialize, Serialize};
+
+/// The sequence of levels making up the game, loaded from `resources/campaign.ron`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Campaign {
+    /// Paths of the level maps, relative to the assets directory.
+    pub levels: Vec<String>,
+    /// Number of lives the marine starts the campaign with.
+    #[serde(default = "default_lives")]
+    pub lives: u32,
+    /// Lives left before the game is over.
+    #[serde(skip)]
+    pub lives_left: u32,
+    /// Index of the level being played.
+    #[serde(skip)]
+    pub current: usize,
+    /// Set once the marine reaches the exit of the current level.
+    #[serde(skip)]
+    pub exit_reached: bool,
+    /// Points earned by the marine since the campaign started.
+    #[serde(skip)]
+    pub score: u32,
+}
+
+impl Campaign {
+    /// Loads the campaign, which has to list at least one level.
+    pub fn load<P: AsRef<Path>>(path: P) -> amethyst::Result<Self> {
+        let campaign = <Self as Config>::load(path)?;
+        if campaign.levels.is_empty() {
+            return Err(amethyst::Error::from_string("The campaign has no levels"));
+        }
+        Ok(campaign)
+    }
+
+    /// Returns the map path of the level being played.
+    pub fn current_level(&self) -> &str {
+        &self.levels[self.current]
+    }
+
+    pub fn is_last_level(&self) -> bool {
+        self.current + 1 >= self.levels.len()
+    }
+
+    /// Moves on to the next level.
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1).min(self.levels.len().saturating_sub(1));
+        self.exit_reached = false;
+    }
+
+    /// Starts the campaign over from its first level.
+    pub fn restart(&mut self) {
+        self.current = 0;
+        self.exit_reached = false;
+        self.lives_left = self.lives;
+        self.score = 0;
+    }
+
+    /// Takes a life away from the marine. Returns `false` once there are no lives left.
+    pub fn lose_life(&mut self) -> bool {
+        self.lives_left = self.lives_left.saturating_sub(1);
+        self.lives_left > 0
+    }
+}
+
+fn default_lives() -> u32 {
+    3
+}
@@ -1,8 +1,18 @@
+use amethyst::window::ScreenDimensions;
+
+use crate::resources::Map;
+
+/// Describes how the loaded map is laid out in the world and on the screen.
+///
+/// The placement of the map (`x_correction`, `y_correction`) is computed once, when the map is
+/// loaded, so that its left and bottom edges line up with the edges of the window. The screen
+/// dimensions follow the window size and are used to keep the camera inside the map.
 #[derive(Clone, Copy, Default)]
 pub struct Context {
     pub map_width: f32,
-    pub bg_width: f32,
     pub bg_height: f32,
+    pub screen_width: f32,
+    pub screen_height: f32,
     pub x_correction: f32,
     pub y_correction: f32,
     pub bg_z_translation: f32,
@@ -12,17 +22,56 @@ pub struct Context {
 }
 
 impl Context {
-    pub fn new() -> Self {
+    pub fn new(map: &Map, screen_dimensions: &ScreenDimensions) -> Self {
+        let scale = 2.;
+        let screen_width = screen_dimensions.width();
+        let screen_height = screen_dimensions.height();
+        let bg_height = (map.height * map.tileheight) as f32;
+        // Width of a background image, or of the screen if the map has no background.
+        let bg_width = map
+            .layers
+            .iter()
+            .find(|layer| layer.name == "background")
+            .and_then(|layer| layer.objects.first())
+            .map_or(screen_width / 2., |obj| obj.width);
+
         Context {
-            map_width: 4608.,
-            bg_width: 384.,
-            bg_height: 352.,
-            x_correction: -(1200. / 2. - 384.), // - (screen_width / 2. - background_width)
-            y_correction: -176.,                // (background_height / 2.) * -1.
+            map_width: (map.width * map.tilewidth) as f32 * scale,
+            bg_height,
+            screen_width,
+            screen_height,
+            x_correction: -(screen_width / 2. - bg_width),
+            y_correction: bg_height / 2. - screen_height / 2.,
             bg_z_translation: -50.,
             truss_z_translation: -40.,
             platform_z_translation: -10.,
-            scale: 2.,
+            scale,
+        }
+    }
+
+    /// Updates the screen dimensions after the window is resized.
+    /// The map stays where it is, only the area visible through the camera changes.
+    pub fn resize(&mut self, screen_dimensions: &ScreenDimensions) {
+        self.screen_width = screen_dimensions.width();
+        self.screen_height = screen_dimensions.height();
+    }
+
+    /// Returns the horizontal position of the camera closest to `x` which doesn't show
+    /// anything beyond the edges of the map.
+    pub fn clamp_camera_x(&self, x: f32) -> f32 {
+        let min_x = self.x_correction + self.screen_width / 2.;
+        let max_x = self.x_correction + self.map_width - self.screen_width / 2.;
+        if min_x > max_x {
+            // The whole map fits in the window, keep it centered.
+            (min_x + max_x) / 2.
+        } else {
+            x.max(min_x).min(max_x)
         }
     }
+
+    /// Returns the vertical position of the camera which aligns the bottom of the map with the
+    /// bottom of the window.
+    pub fn camera_y(&self) -> f32 {
+        self.y_correction + self.screen_height / 2.
+    }
 }
@@ -0,0 +1,32 @@
+use amethyst::{core::math::Vector2, ecs::Entity};
+
+/// Sent when an entity runs out of health.
+#[derive(Clone, Copy, Debug)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    /// The entity credited with the kill, the owner of the projectile or the enemy which ran
+    /// into the marine.
+    pub killer: Option<Entity>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollisionAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// Sent by the `CollisionSystem` for every contact found during a frame.
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    /// The moving entity which ran into `other`.
+    pub entity: Entity,
+    /// The entity collided with, `None` for the level boundaries.
+    pub other: Option<Entity>,
+    pub axis: CollisionAxis,
+    /// How deep `entity` went into `other` along `axis`.
+    pub penetration: f32,
+    /// Direction in which `entity` has to move to get out of `other`.
+    pub normal: Vector2<f32>,
+    /// Position of the bounding box of `entity` when it came in contact with `other`.
+    pub contact_position: Vector2<f32>,
+}
@@ -0,0 +1,37 @@
+/// Duration of a physics step, in seconds. Velocities are in world units per second and
+/// accelerations in world units per second squared.
+pub const STEP_SECONDS: f32 = 1. / 60.;
+
+/// Caps the number of steps run during a single frame, so that a very long frame (e.g. while
+/// the window is dragged) doesn't leave the game further and further behind.
+const MAX_STEPS_PER_FRAME: u32 = 5;
+
+/// Accumulates the time elapsed between the frames and turns it into fixed physics steps.
+///
+/// The game therefore plays the same whatever the frame rate, while the sprites are drawn
+/// between their positions of the last two steps.
+#[derive(Default)]
+pub struct FixedStep {
+    accumulator: f32,
+    alpha: f32,
+}
+
+impl FixedStep {
+    /// Adds the duration of a frame and returns the number of physics steps to run.
+    pub fn advance(&mut self, delta_seconds: f32) -> u32 {
+        self.accumulator += delta_seconds;
+        let steps = ((self.accumulator / STEP_SECONDS) as u32).min(MAX_STEPS_PER_FRAME);
+        self.accumulator -= steps as f32 * STEP_SECONDS;
+        if steps == MAX_STEPS_PER_FRAME {
+            // Give up on catching up with the time which is left.
+            self.accumulator = self.accumulator.min(STEP_SECONDS);
+        }
+        self.alpha = self.accumulator / STEP_SECONDS;
+        steps
+    }
+
+    /// How far the current frame is between the last two physics steps, from `0.` to `1.`.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+}
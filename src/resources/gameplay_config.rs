@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use crate::resources::AssetType;
+
+/// Tuning of the movements and of the enemies' senses, loaded from `resources/gameplay.ron`.
+///
+/// The file is watched while the game runs and reloaded as soon as it changes, so the game feel
+/// can be tuned without recompiling. Speeds are in world units per second and accelerations in
+/// world units per second squared.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameplayConfig {
+    pub marine: MarineConfig,
+    pub pincer: EnemyConfig,
+    pub flier: EnemyConfig,
+    pub turret: EnemyConfig,
+    pub navigation: NavigationConfig,
+}
+
+impl Default for GameplayConfig {
+    fn default() -> Self {
+        GameplayConfig {
+            marine: MarineConfig::default(),
+            pincer: EnemyConfig::default(),
+            flier: EnemyConfig {
+                // A bit faster than the pincer since it's easier to dodge.
+                speed: 240.,
+                // Flying above the marine, it has a wider view.
+                perception: PerceptionConfig {
+                    sight_range: 560.,
+                    sight_angle: 160.,
+                    hearing_radius: 96.,
+                    attention_span: 4.,
+                },
+                pass_through_enemies: false,
+                // Keeps a flock from piling up on the marine.
+                separation_radius: 96.,
+                separation_speed: 120.,
+            },
+            // Turrets don't move, they turn toward the marine and fire at it.
+            turret: EnemyConfig {
+                speed: 0.,
+                perception: PerceptionConfig {
+                    sight_range: 640.,
+                    sight_angle: 180.,
+                    hearing_radius: 128.,
+                    attention_span: 2.,
+                },
+                pass_through_enemies: false,
+                separation_radius: 0.,
+                separation_speed: 0.,
+            },
+            navigation: NavigationConfig::default(),
+        }
+    }
+}
+
+impl GameplayConfig {
+    /// Returns the tuning of an enemy kind, `None` for the assets which aren't enemies.
+    pub fn enemy(&self, kind: AssetType) -> Option<&EnemyConfig> {
+        match kind {
+            AssetType::Pincer => Some(&self.pincer),
+            AssetType::Flier => Some(&self.flier),
+            AssetType::Turret => Some(&self.turret),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MarineConfig {
+    pub gravity: f32,
+    /// Horizontal acceleration while running.
+    pub run_acceleration: f32,
+    /// Horizontal deceleration once the player stops running.
+    pub ground_friction: f32,
+    /// Horizontal deceleration while in the air.
+    pub air_drag: f32,
+    /// Vertical velocity given by a jump.
+    pub jump_velocity: f32,
+    /// Vertical velocity a jump is cut down to when the jump key is released early.
+    pub jump_cut_velocity: f32,
+    /// How long the marine can still jump after walking off a ledge, in seconds.
+    pub coyote_time: f32,
+    /// How long a jump press is remembered before the marine lands, in seconds.
+    pub jump_buffer_time: f32,
+    /// Vertical velocity given by the death, before the marine falls out of the level.
+    pub death_bounce_velocity: f32,
+    pub max_ground_speed: f32,
+    pub max_air_speed: f32,
+}
+
+impl Default for MarineConfig {
+    fn default() -> Self {
+        MarineConfig {
+            gravity: 2160.,
+            run_acceleration: 2160.,
+            ground_friction: 2160.,
+            air_drag: 216.,
+            jump_velocity: 840.,
+            jump_cut_velocity: 300.,
+            coyote_time: 0.1,
+            jump_buffer_time: 0.1,
+            death_bounce_velocity: 480.,
+            max_ground_speed: 360.,
+            max_air_speed: 720.,
+        }
+    }
+}
+
+/// Tuning of an enemy kind, the way it behaves being described by its behavior tree.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EnemyConfig {
+    /// Horizontal speed of the actions moving the enemy around.
+    pub speed: f32,
+    pub perception: PerceptionConfig,
+    /// Whether the enemy goes through the other enemies rather than bumping into them. Only
+    /// applies to the enemies spawned after the change.
+    pub pass_through_enemies: bool,
+    /// Distance under which the enemy steers away from the other enemies of its kind, zero to
+    /// let them bunch up.
+    pub separation_radius: f32,
+    /// Speed the enemy steers away at, when right on top of another one.
+    pub separation_speed: f32,
+}
+
+impl Default for EnemyConfig {
+    fn default() -> Self {
+        EnemyConfig {
+            speed: 180.,
+            perception: PerceptionConfig {
+                sight_range: 480.,
+                sight_angle: 120.,
+                hearing_radius: 96.,
+                attention_span: 3.,
+            },
+            pass_through_enemies: false,
+            separation_radius: 0.,
+            separation_speed: 0.,
+        }
+    }
+}
+
+/// Reach of the ground enemies, used to build the `NavGraph` they find their way with. Only
+/// applies to the levels loaded after the change.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NavigationConfig {
+    /// Room needed above the ground to walk on it.
+    pub clearance: f32,
+    /// Highest a jump climbs onto another surface.
+    pub max_jump_height: f32,
+    /// Widest gap a jump clears.
+    pub max_jump_distance: f32,
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        NavigationConfig {
+            clearance: 64.,
+            max_jump_height: 128.,
+            max_jump_distance: 192.,
+        }
+    }
+}
+
+/// How an enemy notices the marine. Distances are in world units.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct PerceptionConfig {
+    /// How far the enemy sees, as long as no wall stands in between.
+    pub sight_range: f32,
+    /// Width of the sight cone, centered on the way the enemy faces, in degrees.
+    pub sight_angle: f32,
+    /// Distance under which the enemy notices the marine, wherever it looks and through walls.
+    pub hearing_radius: f32,
+    /// How long the enemy keeps after a target it no longer perceives, in seconds.
+    pub attention_span: f32,
+}
@@ -13,21 +13,28 @@ use amethyst::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    components::{Collider, Direction, Motion, Parallax},
-    resources::{AssetType, Context, SpriteSheetList},
+    components::{
+        Boundary, Collider, CollisionLayers, Direction, Directions, Level, Motion, Parallax,
+    },
+    entities::{
+        load_checkpoint, load_exit, load_flier, load_marine, load_pickup, load_pincer, load_turret,
+        EnemySpawn,
+    },
+    resources::{
+        AssetType, BehaviorLibrary, Context, GameplayConfig, NavGraph, PrefabList, Properties,
+        Property, Respawn, SpatialGrid, SpriteSheetList,
+    },
 };
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
-pub struct Property {
-    pub name: String,
-    pub value: usize,
-}
-
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Object {
+    #[serde(default)]
+    pub id: u32,
     pub width: f32,
     pub height: f32,
     pub name: String,
+    #[serde(rename = "type", default)]
+    pub object_type: String,
     pub rotation: f32,
     pub visible: bool,
     pub x: f32,
@@ -43,6 +50,19 @@ pub struct Layer {
     pub x: f32,
     pub y: f32,
     pub objects: Vec<Object>,
+    pub properties: Option<Vec<Property>>,
+}
+
+impl Properties for Object {
+    fn properties(&self) -> &[Property] {
+        self.properties.as_deref().unwrap_or(&[])
+    }
+}
+
+impl Properties for Layer {
+    fn properties(&self) -> &[Property] {
+        self.properties.as_deref().unwrap_or(&[])
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -67,21 +87,40 @@ impl From<Map> for Result<ProcessingState<Map>, Error> {
 }
 
 impl Map {
-    pub fn load_layers(&self, world: &mut World, ctx: &Context) {
+    /// Creates the entities of every layer. Maps without a `player` object in their `spawn`
+    /// layer are rejected before anything gets created.
+    pub fn load_layers(&self, world: &mut World, ctx: &Context) -> Result<(), Error> {
+        let has_player = self
+            .layers
+            .iter()
+            .filter(|layer| layer.name == "spawn")
+            .flat_map(|layer| layer.objects.iter())
+            .any(|obj| obj.object_type == "player");
+        if !has_player {
+            return Err(Error::from_string("The map has no player spawn"));
+        }
+
         for layer in self.layers.iter() {
             match layer.name.as_ref() {
                 "collision" => {
                     self.load_collision_layer(world, layer, ctx);
                 }
+                "spawn" => {
+                    self.load_spawn_layer(world, layer, ctx);
+                }
                 _ => {
                     self.load_non_collision_layer(world, layer, ctx);
                 }
             }
         }
+        Ok(())
     }
 
+    /// Creates the static colliders of the `collision` layer, and the `NavGraph` of the
+    /// surfaces on top of the terrain ones.
     fn load_collision_layer(&self, world: &mut World, layer: &Layer, ctx: &Context) {
         let scale = ctx.scale;
+        let mut terrain = Vec::new();
 
         for obj in layer.objects.iter() {
             let mut transform = Transform::default();
@@ -95,15 +134,32 @@ impl Map {
             );
             bbox.old_position = bbox.position;
 
-            world
+            apply_collision_properties(&mut collider, obj);
+            let (min, max) = collider.bounds();
+            if collider.layer.intersects(CollisionLayers::TERRAIN) {
+                terrain.push((min, max));
+            }
+
+            let entity = world
                 .create_entity()
                 .named("Collision")
+                .with(Level::default())
                 .with(Motion::new())
                 .with(transform)
                 .with(collider)
                 .with(Direction::default())
                 .build();
+            // The terrain never moves, it only has to be added to the broadphase once.
+            world
+                .write_resource::<SpatialGrid>()
+                .insert_static(entity, min, max);
         }
+
+        let nav_graph = NavGraph::new(
+            &terrain,
+            &world.read_resource::<GameplayConfig>().navigation,
+        );
+        world.insert(nav_graph);
     }
 
     fn load_non_collision_layer(&self, world: &mut World, layer: &Layer, ctx: &Context) {
@@ -138,22 +194,11 @@ impl Map {
             for obj in layer.objects.iter() {
                 let mut transform = Transform::default();
 
-                let sprite_index_prop = match &obj.properties {
-                    Some(props) => props.iter().find(|prop| prop.name == "spriteindex"),
-                    None => None,
-                };
-                let mut sprite = SpriteRender {
+                let sprite = SpriteRender {
                     sprite_sheet: sprite_sheet_handle.clone(),
-                    sprite_number: 0,
+                    sprite_number: obj.int_property("spriteindex").unwrap_or(0) as usize,
                 };
 
-                if let Some(prop) = sprite_index_prop {
-                    sprite = SpriteRender {
-                        sprite_sheet: sprite_sheet_handle.clone(),
-                        sprite_number: prop.value,
-                    };
-                }
-
                 match layer.name.as_ref() {
                     "background" | "truss" => {
                         transform.set_translation_xyz(
@@ -168,6 +213,7 @@ impl Map {
                             .with(sprite)
                             .with(Transparent)
                             .with(Parallax::default())
+                            .with(Level::default())
                             .build();
                     }
                     "platform" => {
@@ -183,6 +229,7 @@ impl Map {
                             .with(transform)
                             .with(sprite)
                             .with(Transparent)
+                            .with(Level::default())
                             .build();
                     }
                     _ => {}
@@ -190,4 +237,191 @@ impl Map {
             }
         }
     }
+    /// Creates the player, the enemies, the pickups, the checkpoints and the exits placed in the
+    /// `spawn` object layer.
+    ///
+    /// The object's `type` decides what gets spawned (`player`, `enemy`, `pickup`, `checkpoint`
+    /// or `exit`)
+    /// while its custom properties fine tune the entity:
+    /// * `kind`: enemy kind, either `pincer`, `flier` or `turret`
+    /// * `facing`: `left` or `right`, the initial direction of an enemy
+    /// * `patrol_left` / `patrol_right`: x coordinates (in map pixels) an enemy patrols between,
+    ///   ignored by the turrets which never move
+    /// * `patrol_top` / `patrol_bottom`: y coordinates (in map pixels) a flier stays between
+    /// * `hit_points`: number of bullet hits an enemy can take, or of enemy contacts for the
+    ///   player
+    /// * `damage`: damage dealt by an enemy when it touches the player
+    /// * `points`: points earned by killing an enemy
+    /// * `behavior`: behavior tree an enemy acts out, the name of a file in `assets/behaviors`
+    ///   without its extension, the one named after its kind by default
+    /// * `spriteindex`: sprite used for a pickup
+    ///
+    /// The `collision_layer` and `collision_mask` properties override the collision layers of
+    /// any spawned entity, as well as those of the `collision` layer objects.
+    fn load_spawn_layer(&self, world: &mut World, layer: &Layer, ctx: &Context) {
+        for obj in layer.objects.iter() {
+            let position = spawn_position(obj, ctx);
+
+            let entity = match obj.object_type.as_ref() {
+                "player" => {
+                    let marine_prefab_handle = {
+                        let prefab_list = world.read_resource::<PrefabList>();
+                        prefab_list.get(AssetType::Marine).unwrap().clone()
+                    };
+                    let hit_points = obj
+                        .int_property("hit_points")
+                        .map_or(3, |hp| hp.max(1) as u32);
+                    // The marine comes back here until it reaches a checkpoint.
+                    world.insert(Respawn::new(position, hit_points));
+                    load_marine(world, marine_prefab_handle, position, hit_points, ctx)
+                }
+                "enemy" => {
+                    let kind = obj.string_property("kind");
+                    let (asset_type, default_hit_points, default_points) = match kind {
+                        Some("pincer") => (AssetType::Pincer, 4, 100),
+                        Some("flier") => (AssetType::Flier, 6, 150),
+                        Some("turret") => (AssetType::Turret, 3, 200),
+                        _ => {
+                            warn!(
+                                "Unknown enemy kind {:?} for spawn object {}",
+                                kind, obj.name
+                            );
+                            continue;
+                        }
+                    };
+                    let kind = kind.unwrap_or_default();
+                    let behavior = match obj.string_property("behavior") {
+                        Some(behavior)
+                            if !world.read_resource::<BehaviorLibrary>().contains(behavior) =>
+                        {
+                            warn!(
+                                "Unknown behavior {:?} for spawn object {}, using the {} one",
+                                behavior, obj.name, kind
+                            );
+                            kind
+                        }
+                        behavior => behavior.unwrap_or(kind),
+                    };
+                    let spawn = EnemySpawn {
+                        position,
+                        facing: match obj.string_property("facing") {
+                            Some("right") => Directions::Right,
+                            _ => Directions::Left,
+                        },
+                        boundary: Boundary::new(
+                            obj.float_property("patrol_left")
+                                .map_or(ctx.x_correction, |left| scale_x(left, ctx)),
+                            obj.float_property("patrol_right")
+                                .map_or(ctx.x_correction + ctx.map_width, |right| {
+                                    scale_x(right, ctx)
+                                }),
+                            obj.float_property("patrol_top")
+                                .map_or(ctx.bg_height * 2. + ctx.y_correction, |top| {
+                                    scale_y(top, ctx)
+                                }),
+                            obj.float_property("patrol_bottom")
+                                .map_or(ctx.y_correction, |bottom| scale_y(bottom, ctx)),
+                        ),
+                        hit_points: obj
+                            .int_property("hit_points")
+                            .map_or(default_hit_points, |hp| hp.max(1) as u32),
+                        damage: obj
+                            .int_property("damage")
+                            .map_or(1, |damage| damage.max(0) as u32),
+                        points: obj
+                            .int_property("points")
+                            .map_or(default_points, |points| points.max(0) as u32),
+                        behavior: behavior.to_string(),
+                    };
+
+                    let prefab_handle = {
+                        let prefab_list = world.read_resource::<PrefabList>();
+                        prefab_list.get(asset_type).unwrap().clone()
+                    };
+                    match asset_type {
+                        AssetType::Pincer => load_pincer(world, prefab_handle, spawn, ctx),
+                        AssetType::Flier => load_flier(world, prefab_handle, spawn, ctx),
+                        _ => load_turret(world, prefab_handle, spawn, ctx),
+                    }
+                }
+                "pickup" => {
+                    let pickup_sprite_sheet_handle = {
+                        let sprite_sheet_list = world.read_resource::<SpriteSheetList>();
+                        sprite_sheet_list.get(AssetType::Pickup).unwrap().clone()
+                    };
+                    load_pickup(
+                        world,
+                        pickup_sprite_sheet_handle,
+                        position,
+                        obj.string_property("kind").unwrap_or_default(),
+                        obj.int_property("spriteindex").unwrap_or(0) as usize,
+                        ctx,
+                    )
+                }
+                "checkpoint" => load_checkpoint(
+                    world,
+                    position,
+                    obj.width * ctx.scale,
+                    obj.height * ctx.scale,
+                ),
+                "exit" => load_exit(
+                    world,
+                    position,
+                    obj.width * ctx.scale,
+                    obj.height * ctx.scale,
+                ),
+                object_type => {
+                    warn!(
+                        "Unknown spawn type {:?} for spawn object {}",
+                        object_type, obj.name
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(collider) = world.write_storage::<Collider>().get_mut(entity) {
+                apply_collision_properties(collider, obj);
+            }
+        }
+    }
+}
+
+/// Overrides the collision layers of a collider with the `collision_layer` and
+/// `collision_mask` properties of its map object, e.g. `enemy` and `terrain|player`.
+fn apply_collision_properties(collider: &mut Collider, obj: &Object) {
+    let parse = |name: &str| {
+        obj.string_property(name)
+            .and_then(|layers| match CollisionLayers::parse(layers) {
+                Some(layers) => Some(layers),
+                None => {
+                    warn!("Invalid {} {:?} for map object {}", name, layers, obj.name);
+                    None
+                }
+            })
+    };
+    if let Some(layer) = parse("collision_layer") {
+        collider.layer = layer;
+    }
+    if let Some(mask) = parse("collision_mask") {
+        collider.mask = mask;
+    }
+}
+
+/// Converts a horizontal map coordinate to the world coordinate system.
+fn scale_x(x: f32, ctx: &Context) -> f32 {
+    ctx.scale.mul_add(x, ctx.x_correction)
+}
+
+/// Converts a vertical map coordinate to the world coordinate system.
+fn scale_y(y: f32, ctx: &Context) -> f32 {
+    ctx.bg_height * 2. - y * ctx.scale + ctx.y_correction
+}
+
+/// Returns the world position of the center of a spawn object.
+/// Point objects have no size, so their center is simply their position.
+fn spawn_position(obj: &Object, ctx: &Context) -> Vector2<f32> {
+    Vector2::new(
+        scale_x(obj.x + obj.width / 2., ctx),
+        scale_y(obj.y + obj.height / 2., ctx),
+    )
 }
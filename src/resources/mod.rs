@@ -1,10 +1,32 @@
 mod asset;
+mod behavior;
+mod campaign;
 mod context;
+mod events;
+mod fixed_step;
+mod gameplay_config;
 mod map;
+mod navigation;
+mod property;
+mod respawn;
+mod run_state;
+mod spatial_grid;
+mod weapons;
 
 pub use self::asset::load_assets;
 pub use self::asset::AssetType;
 pub use self::asset::PrefabList;
 pub use self::asset::SpriteSheetList;
+pub use self::behavior::{Action, BehaviorLibrary, BehaviorNode, Condition, Status};
+pub use self::campaign::Campaign;
 pub use self::context::Context;
-pub use self::map::{Layer, Map, Object, Property};
+pub use self::events::{CollisionAxis, CollisionEvent, DeathEvent};
+pub use self::fixed_step::{FixedStep, STEP_SECONDS};
+pub use self::gameplay_config::{GameplayConfig, NavigationConfig, PerceptionConfig};
+pub use self::map::{Layer, Map, Object};
+pub use self::navigation::{LinkKind, NavGraph};
+pub use self::property::{Properties, Property, PropertyValue};
+pub use self::respawn::Respawn;
+pub use self::run_state::RunState;
+pub use self::spatial_grid::SpatialGrid;
+pub use self::weapons::{ProjectileDefinition, WeaponDefinition, WeaponList};
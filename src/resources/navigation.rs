@@ -0,0 +1,430 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use amethyst::core::math::Vector2;
+
+use crate::resources::NavigationConfig;
+
+/// Distance under which the feet of an entity count as standing on a surface, in world units.
+const SURFACE_TOLERANCE: f32 = 4.;
+/// How far past an edge an enemy walking off it lands, about half the width of an enemy.
+const EDGE_MARGIN: f32 = 48.;
+/// Extra cost of a jump, so that walking or dropping down is preferred when it's not much
+/// longer.
+const JUMP_COST: f32 = 64.;
+/// Height a jump clears the surface it lands on by.
+const JUMP_CLEARANCE: f32 = 16.;
+
+/// A stretch of ground the enemies can walk along: the top of a terrain collider, minus the
+/// parts with no room to stand.
+#[derive(Clone, Copy, Debug)]
+pub struct Surface {
+    pub left: f32,
+    pub right: f32,
+    /// Altitude of the ground.
+    pub y: f32,
+}
+
+impl Surface {
+    fn contains(&self, x: f32) -> bool {
+        self.left - SURFACE_TOLERANCE <= x && x <= self.right + SURFACE_TOLERANCE
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinkKind {
+    /// Walks off the edge and falls onto the other surface.
+    Drop,
+    /// Jumps over to the other surface.
+    Jump,
+}
+
+/// A way from one surface to another.
+#[derive(Clone, Copy, Debug)]
+pub struct Link {
+    pub kind: LinkKind,
+    pub from: usize,
+    pub to: usize,
+    /// Where the move starts, on the `from` surface.
+    pub take_off: Vector2<f32>,
+    /// Where it ends, on the `to` surface.
+    pub landing: Vector2<f32>,
+}
+
+impl Link {
+    fn cost(&self) -> f32 {
+        let distance = (self.landing - self.take_off).norm();
+        match self.kind {
+            LinkKind::Drop => distance,
+            LinkKind::Jump => distance + JUMP_COST,
+        }
+    }
+
+    /// Returns the vertical velocity a jump needs to go from the take-off to the landing,
+    /// running at `speed` while `gravity` pulls it down.
+    pub fn jump_velocity(&self, speed: f32, gravity: f32) -> f32 {
+        let height = self.landing.y - self.take_off.y;
+        // High enough to get above the landing...
+        let velocity = (2. * gravity * (height.max(0.) + JUMP_CLEARANCE)).sqrt();
+        // ...and long enough to get there.
+        let duration = (self.landing.x - self.take_off.x).abs() / speed.max(std::f32::EPSILON);
+        if duration > 0. {
+            velocity.max(gravity * duration / 2. + height / duration)
+        } else {
+            velocity
+        }
+    }
+}
+
+/// The walkable surfaces of the level and the links between them, built from the terrain of the
+/// map's `collision` layer.
+///
+/// Walking along a surface is free of obstacles by construction, while getting to another one
+/// takes a `Link`: dropping off an edge onto the highest surface below it, or jumping to a
+/// surface within reach. The jumps only account for the reach set in the `NavigationConfig`,
+/// not for the terrain they could bump into on their way.
+#[derive(Default)]
+pub struct NavGraph {
+    surfaces: Vec<Surface>,
+    links: Vec<Link>,
+    /// Indices in `links` of the links leaving each surface.
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl NavGraph {
+    /// Builds the graph of the terrain `blocks`, given by their lower left and upper right
+    /// corners.
+    pub fn new(blocks: &[(Vector2<f32>, Vector2<f32>)], config: &NavigationConfig) -> Self {
+        let surfaces = find_surfaces(blocks, config.clearance);
+        let mut links = Vec::new();
+
+        for (from, surface) in surfaces.iter().enumerate() {
+            for &(edge, side) in &[(surface.left, -1.), (surface.right, 1.)] {
+                let x = edge + side * EDGE_MARGIN;
+                if let Some(to) = highest_surface(&surfaces, x, surface.y - SURFACE_TOLERANCE) {
+                    links.push(Link {
+                        kind: LinkKind::Drop,
+                        from,
+                        to,
+                        take_off: Vector2::new(edge, surface.y),
+                        landing: Vector2::new(x, surfaces[to].y),
+                    });
+                }
+            }
+            for (to, other) in surfaces.iter().enumerate() {
+                if to == from || other.y - surface.y > config.max_jump_height {
+                    continue;
+                }
+                if let Some((take_off, landing)) = jump_between(surface, other, config) {
+                    links.push(Link {
+                        kind: LinkKind::Jump,
+                        from,
+                        to,
+                        take_off: Vector2::new(take_off, surface.y),
+                        landing: Vector2::new(landing, other.y),
+                    });
+                }
+            }
+        }
+
+        let mut outgoing = vec![Vec::new(); surfaces.len()];
+        for (index, link) in links.iter().enumerate() {
+            outgoing[link.from].push(index);
+        }
+        NavGraph {
+            surfaces,
+            links,
+            outgoing,
+        }
+    }
+
+    /// Returns the surface under `feet`: the highest one not above them.
+    pub fn surface_at(&self, feet: Vector2<f32>) -> Option<usize> {
+        highest_surface(&self.surfaces, feet.x, feet.y + SURFACE_TOLERANCE)
+    }
+
+    /// Finds the shortest way from the surface under the feet at `from` to the one under the
+    /// feet at `to`, with A*. Returns the links to take one after the other, none when both
+    /// stand on the same surface, or `None` when there's no way there.
+    pub fn find_path(&self, from: Vector2<f32>, to: Vector2<f32>) -> Option<Vec<Link>> {
+        let start = self.surface_at(from)?;
+        let goal = self.surface_at(to)?;
+        if start == goal {
+            return Some(Vec::new());
+        }
+        // Heads for the point of the goal surface under the feet, whether they touch it or not.
+        let to = Vector2::new(to.x, self.surfaces[goal].y);
+
+        // The nodes are the links, reached at their landing, followed by the goal itself.
+        let goal_node = self.links.len();
+        let mut search = Search::new(goal_node + 1);
+
+        for &index in &self.outgoing[start] {
+            let link = &self.links[index];
+            let cost = (link.take_off.x - from.x).abs() + link.cost();
+            search.visit(index, None, cost, (to - link.landing).norm());
+        }
+
+        while let Some(Candidate { cost, node, .. }) = search.open.pop() {
+            if node == goal_node {
+                let mut path = Vec::new();
+                let mut current = search.came_from[goal_node];
+                while let Some(index) = current {
+                    path.push(self.links[index]);
+                    current = search.came_from[index];
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if cost > search.costs[node] {
+                // A cheaper way to this node was found since it was queued.
+                continue;
+            }
+
+            let link = &self.links[node];
+            if link.to == goal {
+                let cost = cost + (to.x - link.landing.x).abs();
+                search.visit(goal_node, Some(node), cost, 0.);
+            }
+            for &index in &self.outgoing[link.to] {
+                let next = &self.links[index];
+                let cost = cost + (next.take_off.x - link.landing.x).abs() + next.cost();
+                search.visit(index, Some(node), cost, (to - next.landing).norm());
+            }
+        }
+        None
+    }
+}
+
+/// State of an A* search over the nodes of a `NavGraph`.
+struct Search {
+    /// Lowest cost found so far to get to each node.
+    costs: Vec<f32>,
+    /// Node each node is best reached from, `None` for the first ones.
+    came_from: Vec<Option<usize>>,
+    open: BinaryHeap<Candidate>,
+}
+
+impl Search {
+    fn new(node_count: usize) -> Self {
+        Search {
+            costs: vec![std::f32::INFINITY; node_count],
+            came_from: vec![None; node_count],
+            open: BinaryHeap::new(),
+        }
+    }
+
+    /// Queues the `node` reached from `previous` for `cost`, `remaining` being the distance
+    /// left from there, unless it was already reached for less.
+    fn visit(&mut self, node: usize, previous: Option<usize>, cost: f32, remaining: f32) {
+        if cost < self.costs[node] {
+            self.costs[node] = cost;
+            self.came_from[node] = previous;
+            self.open.push(Candidate {
+                estimate: cost + remaining,
+                cost,
+                node,
+            });
+        }
+    }
+}
+
+/// A node waiting to be expanded by the A* search, the one with the lowest `estimate` first.
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    /// Cost to get to the node plus the distance left from there, which never overestimates
+    /// since every move costs at least the distance it covers.
+    estimate: f32,
+    cost: f32,
+    node: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .partial_cmp(&self.estimate)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the tops of the `blocks` with at least `clearance` above them, the stretches at the
+/// same altitude which touch being merged.
+fn find_surfaces(blocks: &[(Vector2<f32>, Vector2<f32>)], clearance: f32) -> Vec<Surface> {
+    let mut surfaces = Vec::new();
+    for (index, (min, max)) in blocks.iter().enumerate() {
+        let y = max.y;
+        let mut spans = vec![(min.x, max.x)];
+        for (other_index, (other_min, other_max)) in blocks.iter().enumerate() {
+            if other_index == index || other_max.y <= y || other_min.y >= y + clearance {
+                continue;
+            }
+            spans = spans
+                .into_iter()
+                .flat_map(|(left, right)| {
+                    let mut remaining = Vec::new();
+                    if other_min.x > left {
+                        remaining.push((left, right.min(other_min.x)));
+                    }
+                    if other_max.x < right {
+                        remaining.push((left.max(other_max.x), right));
+                    }
+                    remaining
+                })
+                .filter(|(left, right)| right > left)
+                .collect();
+        }
+        surfaces.extend(
+            spans
+                .into_iter()
+                .map(|(left, right)| Surface { left, right, y }),
+        );
+    }
+
+    surfaces.sort_by(|a, b| {
+        (a.y, a.left)
+            .partial_cmp(&(b.y, b.left))
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut merged: Vec<Surface> = Vec::new();
+    for surface in surfaces {
+        match merged.last_mut() {
+            Some(last)
+                if (last.y - surface.y).abs() <= SURFACE_TOLERANCE
+                    && surface.left <= last.right + SURFACE_TOLERANCE =>
+            {
+                last.right = last.right.max(surface.right);
+            }
+            _ => merged.push(surface),
+        }
+    }
+    merged
+}
+
+/// Returns the highest of the `surfaces` under `x` which isn't above `y`.
+fn highest_surface(surfaces: &[Surface], x: f32, y: f32) -> Option<usize> {
+    surfaces
+        .iter()
+        .enumerate()
+        .filter(|(_, surface)| surface.contains(x) && surface.y <= y)
+        .max_by(|(_, a), (_, b)| a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+        .map(|(index, _)| index)
+}
+
+/// Returns where to take off from `from`, and where to land on `to`, to jump from one to the
+/// other, if it's within reach.
+fn jump_between(from: &Surface, to: &Surface, config: &NavigationConfig) -> Option<(f32, f32)> {
+    // Taking off a bit before the edge leaves room to run, even when a wall stands there.
+    let take_off_margin = EDGE_MARGIN.min((from.right - from.left) / 2.);
+    let landing_margin = EDGE_MARGIN.min((to.right - to.left) / 2.);
+    if to.left >= from.right {
+        // Over the gap on the right.
+        if to.left - from.right > config.max_jump_distance {
+            return None;
+        }
+        Some((from.right - take_off_margin, to.left + landing_margin))
+    } else if to.right <= from.left {
+        // Over the gap on the left.
+        if from.left - to.right > config.max_jump_distance {
+            return None;
+        }
+        Some((from.left + take_off_margin, to.right - landing_margin))
+    } else if to.y > from.y {
+        // Up onto a surface overhanging this one, from either side of it.
+        if from.left < to.left - EDGE_MARGIN {
+            Some((to.left - EDGE_MARGIN, to.left + landing_margin))
+        } else if from.right > to.right + EDGE_MARGIN {
+            Some((to.right + EDGE_MARGIN, to.right - landing_margin))
+        } else {
+            None
+        }
+    } else {
+        // Dropping off an edge gets down there.
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(left: f32, bottom: f32, right: f32, top: f32) -> (Vector2<f32>, Vector2<f32>) {
+        (Vector2::new(left, bottom), Vector2::new(right, top))
+    }
+
+    fn kinds(path: &[Link]) -> Vec<LinkKind> {
+        path.iter().map(|link| link.kind).collect()
+    }
+
+    /// A ledge above the left floor, and a right floor past a gap which can be jumped over.
+    fn graph() -> NavGraph {
+        NavGraph::new(
+            &[
+                block(0., 100., 200., 132.),
+                block(0., 0., 300., 32.),
+                block(400., 0., 700., 32.),
+            ],
+            &NavigationConfig::default(),
+        )
+    }
+
+    #[test]
+    fn path_on_the_same_surface_is_empty() {
+        let path = graph().find_path(Vector2::new(20., 32.), Vector2::new(280., 32.));
+        assert_eq!(path.map(|path| path.len()), Some(0));
+    }
+
+    #[test]
+    fn path_jumps_over_a_gap() {
+        let path = graph()
+            .find_path(Vector2::new(20., 32.), Vector2::new(680., 32.))
+            .unwrap();
+        assert_eq!(kinds(&path), vec![LinkKind::Jump]);
+        assert_eq!(path[0].take_off, Vector2::new(252., 32.));
+        assert_eq!(path[0].landing, Vector2::new(448., 32.));
+    }
+
+    #[test]
+    fn path_drops_off_a_ledge_before_jumping() {
+        let path = graph()
+            .find_path(Vector2::new(100., 132.), Vector2::new(680., 32.))
+            .unwrap();
+        assert_eq!(kinds(&path), vec![LinkKind::Drop, LinkKind::Jump]);
+        assert_eq!(path[0].landing, Vector2::new(248., 32.));
+    }
+
+    #[test]
+    fn path_jumps_back_up_onto_the_ledge() {
+        let path = graph()
+            .find_path(Vector2::new(680., 32.), Vector2::new(100., 132.))
+            .unwrap();
+        assert_eq!(kinds(&path), vec![LinkKind::Jump, LinkKind::Jump]);
+        assert_eq!(path[1].take_off, Vector2::new(248., 32.));
+    }
+
+    #[test]
+    fn unreachable_goals_have_no_path() {
+        let graph = NavGraph::new(
+            &[block(0., 0., 1000., 32.), block(400., 300., 600., 332.)],
+            &NavigationConfig::default(),
+        );
+
+        // Too high to jump up to, though dropping down from there works.
+        assert!(graph
+            .find_path(Vector2::new(100., 32.), Vector2::new(500., 332.))
+            .is_none());
+        let path = graph.find_path(Vector2::new(500., 332.), Vector2::new(100., 32.));
+        assert_eq!(path.map(|path| kinds(&path)), Some(vec![LinkKind::Drop]));
+        // Not standing on anything.
+        assert!(graph
+            .find_path(Vector2::new(100., 32.), Vector2::new(2000., 32.))
+            .is_none());
+    }
+}
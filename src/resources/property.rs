@@ -0,0 +1,374 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use amethyst::renderer::palette::Srgba;
+
+use serde::{Deserialize, Serialize};
+
+/// Value of a Tiled custom property, typed according to the `type` stored next to it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    String(String),
+    Int(i32),
+    Float(f32),
+    Bool(bool),
+    /// `None` when the color is left unset.
+    Color(Option<Srgba>),
+    /// Path of a file, relative to the map.
+    File(String),
+    /// Id of another object in the map, `0` when no object is referenced.
+    Object(u32),
+    /// Instance of a custom class, with the members which differ from the class defaults.
+    /// The map doesn't store the types of the members, they are guessed from their values.
+    Class {
+        class: String,
+        members: Vec<Property>,
+    },
+}
+
+impl PropertyValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::String(value) | PropertyValue::File(value) => Some(value.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i32> {
+        match *self {
+            PropertyValue::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Int properties are accepted as well, since Tiled users tend to mix them up.
+    pub fn as_float(&self) -> Option<f32> {
+        match *self {
+            PropertyValue::Float(value) => Some(value),
+            PropertyValue::Int(value) => Some(value as f32),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            PropertyValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Unset colors are returned as `None`.
+    pub fn as_color(&self) -> Option<Srgba> {
+        match *self {
+            PropertyValue::Color(value) => value,
+            _ => None,
+        }
+    }
+
+    /// Unset object references are returned as `None`.
+    pub fn as_object(&self) -> Option<u32> {
+        match *self {
+            PropertyValue::Object(0) => None,
+            PropertyValue::Object(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the class name and the members of a class property.
+    pub fn as_class(&self) -> Option<(&str, &[Property])> {
+        match self {
+            PropertyValue::Class { class, members } => Some((class.as_ref(), members.as_ref())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawProperty", into = "RawProperty")]
+pub struct Property {
+    pub name: String,
+    pub value: PropertyValue,
+}
+
+/// Gives typed access to the custom properties of a map element (`Object`, `Layer`).
+pub trait Properties {
+    fn properties(&self) -> &[Property];
+
+    fn property(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties()
+            .iter()
+            .find(|prop| prop.name == name)
+            .map(|prop| &prop.value)
+    }
+
+    fn string_property(&self, name: &str) -> Option<&str> {
+        self.property(name).and_then(PropertyValue::as_str)
+    }
+
+    fn int_property(&self, name: &str) -> Option<i32> {
+        self.property(name).and_then(PropertyValue::as_int)
+    }
+
+    fn float_property(&self, name: &str) -> Option<f32> {
+        self.property(name).and_then(PropertyValue::as_float)
+    }
+
+    fn bool_property(&self, name: &str) -> Option<bool> {
+        self.property(name).and_then(PropertyValue::as_bool)
+    }
+
+    fn color_property(&self, name: &str) -> Option<Srgba> {
+        self.property(name).and_then(PropertyValue::as_color)
+    }
+
+    fn object_property(&self, name: &str) -> Option<u32> {
+        self.property(name).and_then(PropertyValue::as_object)
+    }
+
+    fn class_property(&self, name: &str) -> Option<(&str, &[Property])> {
+        self.property(name).and_then(PropertyValue::as_class)
+    }
+}
+
+/// A property the way it is stored in a Tiled json map.
+#[derive(Clone, Deserialize, Serialize)]
+struct RawProperty {
+    name: String,
+    // Older Tiled versions only store the type of non string properties.
+    #[serde(rename = "type", default = "default_property_type")]
+    property_type: String,
+    /// Name of the class of `class` properties.
+    #[serde(
+        rename = "propertytype",
+        default,
+        skip_serializing_if = "String::is_empty"
+    )]
+    class: String,
+    value: RawPropertyValue,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum RawPropertyValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Members(BTreeMap<String, RawPropertyValue>),
+}
+
+fn default_property_type() -> String {
+    String::from("string")
+}
+
+impl TryFrom<RawProperty> for Property {
+    type Error = String;
+
+    fn try_from(raw: RawProperty) -> Result<Self, Self::Error> {
+        let RawProperty {
+            name,
+            property_type,
+            class,
+            value,
+        } = raw;
+        let value = match (property_type.as_ref(), value) {
+            ("string", RawPropertyValue::Text(value)) => PropertyValue::String(value),
+            ("file", RawPropertyValue::Text(value)) => PropertyValue::File(value),
+            ("int", RawPropertyValue::Int(value)) => PropertyValue::Int(
+                i32::try_from(value)
+                    .map_err(|_| format!("Int {} out of range for property {}", value, name))?,
+            ),
+            ("float", RawPropertyValue::Int(value)) => PropertyValue::Float(value as f32),
+            ("float", RawPropertyValue::Float(value)) => PropertyValue::Float(value as f32),
+            ("bool", RawPropertyValue::Bool(value)) => PropertyValue::Bool(value),
+            ("color", RawPropertyValue::Text(value)) => PropertyValue::Color(
+                parse_color(&value)
+                    .ok_or_else(|| format!("Invalid color {:?} for property {}", value, name))?,
+            ),
+            ("object", RawPropertyValue::Int(value)) => PropertyValue::Object(
+                u32::try_from(value)
+                    .map_err(|_| format!("Invalid object id {} for property {}", value, name))?,
+            ),
+            ("class", RawPropertyValue::Members(members)) => PropertyValue::Class {
+                class,
+                members: members
+                    .into_iter()
+                    .map(|(name, value)| Property::try_from(guess_member_type(name, value)))
+                    .collect::<Result<_, _>>()?,
+            },
+            (property_type, _) => {
+                return Err(format!(
+                    "Invalid value for property {} of type {}",
+                    name, property_type
+                ));
+            }
+        };
+        Ok(Property { name, value })
+    }
+}
+
+/// Gives a member of a class property the type matching its value, members of a nested class
+/// being left without a class name.
+fn guess_member_type(name: String, value: RawPropertyValue) -> RawProperty {
+    let property_type = match value {
+        RawPropertyValue::Bool(_) => "bool",
+        RawPropertyValue::Int(_) => "int",
+        RawPropertyValue::Float(_) => "float",
+        RawPropertyValue::Text(_) => "string",
+        RawPropertyValue::Members(_) => "class",
+    };
+    RawProperty {
+        name,
+        property_type: property_type.to_string(),
+        class: String::new(),
+        value,
+    }
+}
+
+impl From<Property> for RawProperty {
+    fn from(property: Property) -> Self {
+        let mut class = String::new();
+        let (property_type, value) = match property.value {
+            PropertyValue::String(value) => ("string", RawPropertyValue::Text(value)),
+            PropertyValue::File(value) => ("file", RawPropertyValue::Text(value)),
+            PropertyValue::Int(value) => ("int", RawPropertyValue::Int(i64::from(value))),
+            PropertyValue::Float(value) => ("float", RawPropertyValue::Float(f64::from(value))),
+            PropertyValue::Bool(value) => ("bool", RawPropertyValue::Bool(value)),
+            PropertyValue::Color(value) => (
+                "color",
+                RawPropertyValue::Text(value.map(format_color).unwrap_or_default()),
+            ),
+            PropertyValue::Object(value) => ("object", RawPropertyValue::Int(i64::from(value))),
+            PropertyValue::Class {
+                class: name,
+                members,
+            } => {
+                class = name;
+                let members = members
+                    .into_iter()
+                    .map(|member| (member.name.clone(), RawProperty::from(member).value))
+                    .collect();
+                ("class", RawPropertyValue::Members(members))
+            }
+        };
+        RawProperty {
+            name: property.name,
+            property_type: property_type.to_string(),
+            class,
+            value,
+        }
+    }
+}
+
+/// Parses a Tiled color, written either as `#AARRGGBB` or as `#RRGGBB`, or left empty when
+/// unset.
+fn parse_color(value: &str) -> Option<Option<Srgba>> {
+    if value.is_empty() {
+        return Some(None);
+    }
+    let hex = value.trim_start_matches('#');
+    let argb = match hex.len() {
+        6 => 0xff00_0000 | u32::from_str_radix(hex, 16).ok()?,
+        8 => u32::from_str_radix(hex, 16).ok()?,
+        _ => return None,
+    };
+    let channel = |shift: u32| ((argb >> shift) & 0xff) as f32 / 255.;
+    Some(Some(Srgba::new(
+        channel(16),
+        channel(8),
+        channel(0),
+        channel(24),
+    )))
+}
+
+fn format_color(color: Srgba) -> String {
+    let channel = |value: f32| (value * 255.).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        channel(color.alpha),
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(name: &str, property_type: &str, value: RawPropertyValue) -> RawProperty {
+        RawProperty {
+            name: name.to_string(),
+            property_type: property_type.to_string(),
+            class: String::new(),
+            value,
+        }
+    }
+
+    fn convert(raw: RawProperty) -> Result<PropertyValue, String> {
+        Property::try_from(raw).map(|property| property.value)
+    }
+
+    #[test]
+    fn values_are_converted_to_their_type() {
+        assert_eq!(
+            convert(raw("speed", "float", RawPropertyValue::Int(3))),
+            Ok(PropertyValue::Float(3.))
+        );
+        assert_eq!(
+            convert(raw(
+                "tint",
+                "color",
+                RawPropertyValue::Text("#80ff0000".into())
+            )),
+            Ok(PropertyValue::Color(Some(Srgba::new(
+                1.,
+                0.,
+                0.,
+                128. / 255.
+            ))))
+        );
+        assert_eq!(
+            convert(raw("tint", "color", RawPropertyValue::Text(String::new()))),
+            Ok(PropertyValue::Color(None))
+        );
+    }
+
+    #[test]
+    fn out_of_range_numbers_are_rejected() {
+        let err = convert(raw("lives", "int", RawPropertyValue::Int(1 << 40))).unwrap_err();
+        assert!(err.contains("lives"));
+        assert!(convert(raw("target", "object", RawPropertyValue::Int(-1))).is_err());
+    }
+
+    #[test]
+    fn invalid_colors_are_rejected() {
+        for value in &["#ff0000f", "#gg0000", "red"] {
+            let err = convert(raw(
+                "tint",
+                "color",
+                RawPropertyValue::Text(value.to_string()),
+            ))
+            .unwrap_err();
+            assert!(err.contains("tint"));
+        }
+    }
+
+    #[test]
+    fn values_not_matching_their_type_are_rejected() {
+        let err = convert(raw("solid", "bool", RawPropertyValue::Int(1))).unwrap_err();
+        assert_eq!(err, "Invalid value for property solid of type bool");
+        assert!(convert(raw("speed", "int", RawPropertyValue::Float(1.5))).is_err());
+        assert!(convert(raw("name", "unknown", RawPropertyValue::Text("a".into()))).is_err());
+    }
+
+    #[test]
+    fn invalid_class_members_are_rejected() {
+        let mut members = BTreeMap::new();
+        members.insert("health".to_string(), RawPropertyValue::Int(1 << 40));
+        let mut class = raw("stats", "class", RawPropertyValue::Members(members));
+        class.class = "Stats".to_string();
+
+        let err = convert(class).unwrap_err();
+        assert!(err.contains("health"));
+    }
+}
@@ -0,0 +1,26 @@
+use amethyst::core::math::Vector2;
+
+/// Where and how the marine comes back to life after losing one of its lives.
+///
+/// Set when the player is spawned with the level, then moved every time the marine reaches a
+/// checkpoint.
+#[derive(Clone, Copy)]
+pub struct Respawn {
+    pub position: Vector2<f32>,
+    pub hit_points: u32,
+}
+
+impl Default for Respawn {
+    fn default() -> Self {
+        Respawn::new(Vector2::new(0., 0.), 0)
+    }
+}
+
+impl Respawn {
+    pub fn new(position: Vector2<f32>, hit_points: u32) -> Self {
+        Respawn {
+            position,
+            hit_points,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// Tells whether the gameplay systems are running. They are only run in the gameplay state and
+/// are frozen in every other state, including while the game is paused.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RunState {
+    Running,
+    Paused,
+}
+
+impl Default for RunState {
+    fn default() -> Self {
+        RunState::Paused
+    }
+}
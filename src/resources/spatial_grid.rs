@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+
+use amethyst::{core::math::Vector2, ecs::Entity};
+
+/// Size of a grid cell, in world units. A few times the size of the marine, so that most
+/// colliders only cover one or two cells.
+const DEFAULT_CELL_SIZE: f32 = 128.;
+
+/// Uniform grid used as the broadphase of the collision detection.
+///
+/// A collider is bucketed in every cell its bounds overlap, and only the colliders sharing a
+/// cell are tested against each other. Static colliders, such as the terrain, are inserted once
+/// when the map is loaded while the moving ones are bucketed again every frame.
+pub struct SpatialGrid {
+    cell_size: f32,
+    static_cells: HashMap<(i32, i32), Vec<Entity>>,
+    dynamic_cells: HashMap<(i32, i32), Vec<Entity>>,
+    statics: HashSet<Entity>,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        SpatialGrid::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialGrid {
+            cell_size,
+            static_cells: HashMap::new(),
+            dynamic_cells: HashMap::new(),
+            statics: HashSet::new(),
+        }
+    }
+
+    /// Adds a collider which never moves. It stays in the grid until the grid is dropped.
+    pub fn insert_static(&mut self, entity: Entity, min: Vector2<f32>, max: Vector2<f32>) {
+        for cell in self.cells(min, max) {
+            self.static_cells.entry(cell).or_default().push(entity);
+        }
+        self.statics.insert(entity);
+    }
+
+    pub fn is_static(&self, entity: Entity) -> bool {
+        self.statics.contains(&entity)
+    }
+
+    /// Empties the cells of the moving colliders, before they are inserted at their new
+    /// position. The cells keep their allocations from one frame to the next.
+    pub fn clear_dynamic(&mut self) {
+        for entities in self.dynamic_cells.values_mut() {
+            entities.clear();
+        }
+    }
+
+    pub fn insert_dynamic(&mut self, entity: Entity, min: Vector2<f32>, max: Vector2<f32>) {
+        for cell in self.cells(min, max) {
+            self.dynamic_cells.entry(cell).or_default().push(entity);
+        }
+    }
+
+    /// Replaces the content of `candidates` with every collider sharing a cell with the given
+    /// bounds, each one listed once.
+    pub fn query(&self, min: Vector2<f32>, max: Vector2<f32>, candidates: &mut Vec<Entity>) {
+        candidates.clear();
+        for cell in self.cells(min, max) {
+            if let Some(entities) = self.static_cells.get(&cell) {
+                candidates.extend_from_slice(entities);
+            }
+            if let Some(entities) = self.dynamic_cells.get(&cell) {
+                candidates.extend_from_slice(entities);
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+    }
+
+    /// Same as `query`, but only lists the static colliders.
+    pub fn query_static(&self, min: Vector2<f32>, max: Vector2<f32>, candidates: &mut Vec<Entity>) {
+        candidates.clear();
+        for cell in self.cells(min, max) {
+            if let Some(entities) = self.static_cells.get(&cell) {
+                candidates.extend_from_slice(entities);
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+    }
+
+    fn cells(&self, min: Vector2<f32>, max: Vector2<f32>) -> impl Iterator<Item = (i32, i32)> {
+        let cell_size = self.cell_size;
+        let (min_x, min_y) = (
+            (min.x / cell_size).floor() as i32,
+            (min.y / cell_size).floor() as i32,
+        );
+        let (max_x, max_y) = (
+            (max.x / cell_size).floor() as i32,
+            (max.y / cell_size).floor() as i32,
+        );
+        (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+    }
+}
@@ -0,0 +1,113 @@
+use std::{collections::HashMap, iter, path::Path};
+
+use amethyst::{
+    config::Config,
+    core::math::{Rotation2, Vector2},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::AssetType;
+
+/// The weapons loaded from `resources/weapons.ron`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WeaponList {
+    /// The weapons the marine carries. The first one is in its hands when it spawns.
+    pub weapons: Vec<WeaponDefinition>,
+    /// The weapon of the turrets.
+    pub turret: WeaponDefinition,
+    /// The projectiles fired by the weapons, by name.
+    pub projectiles: HashMap<String, ProjectileDefinition>,
+}
+
+impl WeaponList {
+    /// Loads the list, making sure that every weapon fires one of its projectiles.
+    pub fn load<P: AsRef<Path>>(path: P) -> amethyst::Result<Self> {
+        let weapon_list = <Self as Config>::load(path)?;
+        let weapons = weapon_list
+            .weapons
+            .iter()
+            .chain(iter::once(&weapon_list.turret));
+        for weapon in weapons {
+            if !weapon_list.projectiles.contains_key(&weapon.projectile) {
+                return Err(amethyst::Error::from_string(format!(
+                    "Unknown projectile {} for the {} weapon",
+                    weapon.projectile, weapon.name
+                )));
+            }
+        }
+        Ok(weapon_list)
+    }
+
+    /// Returns the projectile fired by the `weapon`, which is checked to exist when loading.
+    pub fn projectile(&self, weapon: &WeaponDefinition) -> &ProjectileDefinition {
+        &self.projectiles[&weapon.projectile]
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WeaponDefinition {
+    pub name: String,
+    /// Name of the projectile fired, among the `projectiles` of the list.
+    pub projectile: String,
+    /// Minimum time between two shots, in seconds.
+    pub cooldown: f32,
+    /// Angle the projectiles of a shot are fanned out over, in degrees.
+    #[serde(default)]
+    pub spread: f32,
+    /// Number of projectiles fired by each shot.
+    #[serde(default = "default_burst_count")]
+    pub burst_count: u32,
+    /// Whether the weapon keeps firing while the trigger is held down.
+    #[serde(default)]
+    pub auto_fire: bool,
+    /// Number of shots the weapon holds, unlimited when `None`.
+    #[serde(default)]
+    pub ammo_capacity: Option<u32>,
+    /// How long the trigger has to be held before releasing it fires, in seconds. Zero for the
+    /// weapons firing as soon as the trigger is pulled.
+    #[serde(default)]
+    pub charge_time: f32,
+}
+
+/// A projectile fired by a weapon. Only its look and its numbers are data driven, the entity
+/// itself is put together by `spawn_bullet`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProjectileDefinition {
+    /// Sprite sheet the projectile is drawn with, facing right.
+    pub sprite_sheet: AssetType,
+    /// Size of the projectile, relative to its sprite.
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// Speed of the projectile, in world units per second.
+    pub speed: f32,
+    /// Size of the hit box along and across the flight of the projectile, in sprite pixels.
+    pub hit_box: (f32, f32),
+    /// Health taken away by the projectile.
+    pub damage: u32,
+}
+
+impl WeaponDefinition {
+    /// Returns the unit vectors the projectiles of a shot fly along, fanned out evenly around
+    /// the `aim`.
+    pub fn burst_headings(&self, aim: Vector2<f32>) -> Vec<Vector2<f32>> {
+        let burst_count = self.burst_count.max(1);
+        (0..burst_count)
+            .map(|i| {
+                let angle = if burst_count > 1 {
+                    self.spread * (i as f32 / (burst_count - 1) as f32 - 0.5)
+                } else {
+                    0.
+                };
+                Rotation2::new(angle.to_radians()) * aim
+            })
+            .collect()
+    }
+}
+
+fn default_scale() -> f32 {
+    1.
+}
+
+fn default_burst_count() -> u32 {
+    1
+}
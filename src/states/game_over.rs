@@ -0,0 +1,40 @@
+use amethyst::{
+    ecs::Entity,
+    input::InputEvent,
+    prelude::{GameData, SimpleState, SimpleTrans, StateData, Trans},
+    StateEvent,
+};
+
+use crate::states::{create_ui, delete_ui, unload_level, MenuState};
+
+/// Shown when the marine is dead, goes back to the main menu.
+#[derive(Default)]
+pub struct GameOverState {
+    ui: Option<Entity>,
+}
+
+impl SimpleState for GameOverState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.ui = Some(create_ui(data.world, "ui/game_over.ron"));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(ui) = self.ui.take() {
+            delete_ui(data.world, ui);
+        }
+        unload_level(data.world);
+    }
+
+    fn handle_event(
+        &mut self,
+        _: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Input(InputEvent::ActionPressed(action)) = event {
+            if action == "confirm" {
+                return Trans::Switch(Box::new(MenuState::default()));
+            }
+        }
+        Trans::None
+    }
+}
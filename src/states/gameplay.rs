@@ -0,0 +1,185 @@
+use amethyst::{
+    core::{ArcThreadPool, Time, Transform},
+    ecs::{prelude::World, Dispatcher, DispatcherBuilder, Entity, Join},
+    input::InputEvent,
+    prelude::{GameData, SimpleState, SimpleTrans, StateData, Trans, WorldExt},
+    StateEvent,
+};
+
+use crate::{
+    components::{Marine, Subject},
+    entities::load_marine,
+    resources::{AssetType, Campaign, Context, FixedStep, PrefabList, Respawn, RunState},
+    states::{create_ui, delete_ui, GameOverState, LevelCompleteState, PauseState},
+    systems::*,
+};
+
+/// The level is being played, this is the only state in which the gameplay systems run.
+///
+/// The physics systems have their own dispatcher, run a fixed number of times per second
+/// whatever the frame rate.
+#[derive(Default)]
+pub struct GameplayState {
+    ui: Vec<Entity>,
+    physics: Option<Dispatcher<'static, 'static>>,
+}
+
+impl SimpleState for GameplayState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        self.ui.push(create_ui(world, "ui/fps.ron"));
+        self.ui.push(create_ui(world, "ui/player.ron"));
+        *world.write_resource::<RunState>() = RunState::Running;
+        *world.write_resource::<FixedStep>() = FixedStep::default();
+        self.physics = Some(create_physics_dispatcher(world));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        *data.world.write_resource::<RunState>() = RunState::Paused;
+        self.physics = None;
+        for ui in self.ui.drain(..) {
+            delete_ui(data.world, ui);
+        }
+    }
+
+    fn on_pause(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        *data.world.write_resource::<RunState>() = RunState::Paused;
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        *data.world.write_resource::<RunState>() = RunState::Running;
+    }
+
+    fn handle_event(
+        &mut self,
+        _: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Input(InputEvent::ActionPressed(action)) = event {
+            if action == "pause" {
+                return Trans::Push(Box::new(PauseState::default()));
+            }
+        }
+        Trans::None
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let world = &mut data.world;
+        let steps = {
+            let delta_seconds = world.read_resource::<Time>().delta_seconds();
+            world.write_resource::<FixedStep>().advance(delta_seconds)
+        };
+        if let Some(physics) = self.physics.as_mut() {
+            for _ in 0..steps {
+                physics.dispatch(world);
+                // Spawned and deleted entities, such as bullets, have to be taken into account
+                // by the next step.
+                world.maintain();
+            }
+        }
+
+        if world.read_resource::<Campaign>().exit_reached {
+            return Trans::Switch(Box::new(LevelCompleteState::default()));
+        }
+        if (&world.read_storage::<Marine>()).join().next().is_none() {
+            if !world.write_resource::<Campaign>().lose_life() {
+                return Trans::Switch(Box::new(GameOverState::default()));
+            }
+            respawn_marine(world);
+        }
+        Trans::None
+    }
+}
+
+/// Brings the marine back to life at its respawn point and moves the camera straight there,
+/// instead of letting it scroll through the level.
+fn respawn_marine(world: &mut World) {
+    let respawn = *world.read_resource::<Respawn>();
+    let ctx = *world.read_resource::<Context>();
+    let marine_prefab_handle = {
+        let prefab_list = world.read_resource::<PrefabList>();
+        prefab_list.get(AssetType::Marine).unwrap().clone()
+    };
+    load_marine(
+        world,
+        marine_prefab_handle,
+        respawn.position,
+        respawn.hit_points,
+        &ctx,
+    );
+
+    let subjects = world.read_storage::<Subject>();
+    let mut transforms = world.write_storage::<Transform>();
+    for (_, transform) in (&subjects, &mut transforms).join() {
+        transform.set_translation_x(ctx.clamp_camera_x(respawn.position.x));
+        transform.set_translation_y(ctx.camera_y());
+    }
+}
+
+/// Creates the dispatcher of the systems moving the entities and resolving their collisions.
+fn create_physics_dispatcher(world: &mut World) -> Dispatcher<'static, 'static> {
+    let pool = (*world.read_resource::<ArcThreadPool>()).clone();
+    let mut dispatcher = DispatcherBuilder::new()
+        .with_pool(pool)
+        .with(MarineInputSystem, "marine_input_system", &[])
+        .with(
+            MarineKinematicsSystem,
+            "marine_kinematics_system",
+            &["marine_input_system"],
+        )
+        .with(
+            KinematicsSystem,
+            "kinematics_system",
+            &["marine_kinematics_system"],
+        )
+        .with(
+            AttackSystem::default(),
+            "attack_system",
+            &["kinematics_system"],
+        )
+        .with(
+            PerceptionSystem,
+            "perception_system",
+            &["kinematics_system"],
+        )
+        .with(BehaviorSystem, "behavior_system", &["perception_system"])
+        .with(
+            CollisionSystem,
+            "collision_system",
+            &["attack_system", "behavior_system"],
+        )
+        .with(
+            BulletCollisionSystem::default(),
+            "bullet_collision_system",
+            &["collision_system"],
+        )
+        .with(
+            EnemyCollisionSystem::default(),
+            "enemy_collision_system",
+            &["collision_system"],
+        )
+        .with(
+            MarineCollisionSystem::default(),
+            "marine_collision_system",
+            &["collision_system"],
+        )
+        .with(
+            DamageSystem::default(),
+            "damage_system",
+            &["collision_system", "marine_collision_system"],
+        )
+        .with(ScoreSystem::default(), "score_system", &["damage_system"])
+        .with(
+            TransformationSystem,
+            "transformation_system",
+            &[
+                "marine_collision_system",
+                "damage_system",
+                "enemy_collision_system",
+                "bullet_collision_system",
+            ],
+        )
+        .build();
+    dispatcher.setup(world);
+    dispatcher
+}
@@ -0,0 +1,99 @@
+use amethyst::{
+    assets::{AssetStorage, Handle, JsonFormat, Loader, ProgressCounter},
+    ecs::{prelude::World, Entity, Join},
+    prelude::{GameData, SimpleState, SimpleTrans, StateData, Trans, WorldExt},
+    window::ScreenDimensions,
+};
+
+use crate::{
+    components::Level,
+    resources::{Campaign, Context, Map, NavGraph, Respawn, SpatialGrid},
+    states::{GameplayState, MenuState},
+};
+
+/// Loads the map of the current campaign level, creates its entities and starts playing it.
+#[derive(Default)]
+pub struct LevelLoadState {
+    progress_counter: ProgressCounter,
+    map_handle: Option<Handle<Map>>,
+}
+
+impl SimpleState for LevelLoadState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        // The marine respawns where the new level spawns it, not at the previous level's
+        // checkpoint.
+        world.insert(Respawn::default());
+        let level = world
+            .read_resource::<Campaign>()
+            .current_level()
+            .to_string();
+        self.map_handle = {
+            let loader = world.read_resource::<Loader>();
+            Some(loader.load(
+                level,
+                JsonFormat,
+                &mut self.progress_counter,
+                &world.read_resource::<AssetStorage<Map>>(),
+            ))
+        };
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        if !self.progress_counter.is_complete() {
+            return Trans::None;
+        }
+
+        // A missing or invalid map sends the player back to the menu.
+        let map = {
+            let map_storage = &data.world.read_resource::<AssetStorage<Map>>();
+            self.map_handle
+                .take()
+                .and_then(|map_handle| map_storage.get(&map_handle).cloned())
+        };
+        let map = match map {
+            Some(map) if self.progress_counter.num_failed() == 0 => map,
+            _ => {
+                for err in self.progress_counter.errors() {
+                    error!("Failed to load the map {}: {}", err.asset_name, err.error);
+                }
+                return Trans::Switch(Box::new(MenuState::default()));
+            }
+        };
+        let ctx = Context::new(&map, &data.world.read_resource::<ScreenDimensions>());
+        data.world.insert(ctx);
+        data.world.insert(SpatialGrid::default());
+        data.world.insert(NavGraph::default());
+
+        // Creates the level, including the marine, the enemies and the pickups
+        // placed in its spawn layer.
+        if let Err(err) = map.load_layers(data.world, &ctx) {
+            error!(
+                "Failed to load the level {}: {}",
+                data.world.read_resource::<Campaign>().current_level(),
+                err
+            );
+            return Trans::Switch(Box::new(MenuState::default()));
+        }
+
+        Trans::Switch(Box::new(GameplayState::default()))
+    }
+}
+
+/// Deletes all the entities of the current level.
+pub fn unload_level(world: &mut World) {
+    let level_entities: Vec<Entity> = {
+        let entities = world.entities();
+        let levels = world.read_storage::<Level>();
+        (&entities, &levels)
+            .join()
+            .map(|(entity, _)| entity)
+            .collect()
+    };
+    world
+        .delete_entities(&level_entities)
+        .expect("Failed to delete the level entities");
+    world.insert(SpatialGrid::default());
+    world.insert(NavGraph::default());
+    world.insert(Respawn::default());
+}
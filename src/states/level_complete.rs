@@ -0,0 +1,49 @@
+use amethyst::{
+    ecs::Entity,
+    input::InputEvent,
+    prelude::{GameData, SimpleState, SimpleTrans, StateData, Trans, WorldExt},
+    StateEvent,
+};
+
+use crate::{
+    resources::Campaign,
+    states::{create_ui, delete_ui, unload_level, LevelLoadState, MenuState},
+};
+
+/// Shown when the marine reaches the exit of a level. Moves on to the next level, or back to
+/// the main menu once the whole campaign is completed.
+#[derive(Default)]
+pub struct LevelCompleteState {
+    ui: Option<Entity>,
+}
+
+impl SimpleState for LevelCompleteState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.ui = Some(create_ui(data.world, "ui/level_complete.ron"));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(ui) = self.ui.take() {
+            delete_ui(data.world, ui);
+        }
+        unload_level(data.world);
+    }
+
+    fn handle_event(
+        &mut self,
+        data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Input(InputEvent::ActionPressed(action)) = event {
+            if action == "confirm" {
+                let mut campaign = data.world.write_resource::<Campaign>();
+                if campaign.is_last_level() {
+                    return Trans::Switch(Box::new(MenuState::default()));
+                }
+                campaign.advance();
+                return Trans::Switch(Box::new(LevelLoadState::default()));
+            }
+        }
+        Trans::None
+    }
+}
@@ -0,0 +1,47 @@
+use amethyst::{
+    ecs::Entity,
+    input::InputEvent,
+    prelude::{GameData, SimpleState, SimpleTrans, StateData, Trans, WorldExt},
+    StateEvent,
+};
+
+use crate::{
+    resources::Campaign,
+    states::{create_ui, delete_ui, LevelLoadState},
+};
+
+/// Main menu, starts the campaign from its first level.
+#[derive(Default)]
+pub struct MenuState {
+    ui: Option<Entity>,
+}
+
+impl SimpleState for MenuState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.ui = Some(create_ui(data.world, "ui/menu.ron"));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(ui) = self.ui.take() {
+            delete_ui(data.world, ui);
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Input(InputEvent::ActionPressed(action)) = event {
+            match action.as_ref() {
+                "confirm" => {
+                    data.world.write_resource::<Campaign>().restart();
+                    return Trans::Switch(Box::new(LevelLoadState::default()));
+                }
+                "pause" => return Trans::Quit,
+                _ => {}
+            }
+        }
+        Trans::None
+    }
+}
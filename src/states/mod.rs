@@ -1,3 +1,19 @@
+mod game_over;
+mod gameplay;
+mod level;
+mod level_complete;
 mod load;
+mod menu;
+mod pause;
+mod ui;
 
+pub use self::game_over::GameOverState;
+pub use self::gameplay::GameplayState;
+pub use self::level::unload_level;
+pub use self::level::LevelLoadState;
+pub use self::level_complete::LevelCompleteState;
 pub use self::load::LoadState;
+pub use self::menu::MenuState;
+pub use self::pause::PauseState;
+pub use self::ui::create_ui;
+pub use self::ui::delete_ui;
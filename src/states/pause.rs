@@ -0,0 +1,39 @@
+use amethyst::{
+    ecs::Entity,
+    input::InputEvent,
+    prelude::{GameData, SimpleState, SimpleTrans, StateData, Trans},
+    StateEvent,
+};
+
+use crate::states::{create_ui, delete_ui};
+
+/// Pushed on top of the gameplay state, which stays frozen until this state is popped.
+#[derive(Default)]
+pub struct PauseState {
+    ui: Option<Entity>,
+}
+
+impl SimpleState for PauseState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.ui = Some(create_ui(data.world, "ui/pause.ron"));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(ui) = self.ui.take() {
+            delete_ui(data.world, ui);
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        _: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Input(InputEvent::ActionPressed(action)) = event {
+            if action == "pause" || action == "confirm" {
+                return Trans::Pop;
+            }
+        }
+        Trans::None
+    }
+}
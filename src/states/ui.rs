@@ -0,0 +1,25 @@
+use amethyst::{
+    assets::ProgressCounter,
+    core::ParentHierarchy,
+    ecs::{prelude::World, Entity},
+    prelude::WorldExt,
+    ui::UiCreator,
+};
+
+/// Creates the UI described by the given prefab and returns its root entity.
+pub fn create_ui(world: &mut World, path: &str) -> Entity {
+    let mut progress = ProgressCounter::default();
+    world.exec(|mut creator: UiCreator<'_>| creator.create(path, &mut progress))
+}
+
+/// Deletes a UI created by `create_ui` along with all of its widgets.
+pub fn delete_ui(world: &mut World, root: Entity) {
+    let mut ui_entities: Vec<Entity> = world
+        .read_resource::<ParentHierarchy>()
+        .all_children_iter(root)
+        .collect();
+    ui_entities.push(root);
+    world
+        .delete_entities(&ui_entities)
+        .expect("Failed to delete the UI entities");
+}
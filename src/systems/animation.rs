@@ -7,7 +7,8 @@ use amethyst::{
 };
 
 use crate::components::{
-    Animation, AnimationId, BulletImpact, Explosion, Flier, Marine, MarineState, Motion, Pincer,
+    Animation, AnimationId, BulletImpact, Explosion, Flier, Marine, MarineAction, MarineState,
+    Motion, Pincer,
 };
 
 pub struct BulletImpactAnimationSystem;
@@ -119,6 +120,7 @@ impl<'s> System<'s> for AnimationControlSystem {
 
                         let end = match animation_id {
                             AnimationId::Shoot
+                            | AnimationId::JumpShoot
                             | AnimationId::Explode
                             | AnimationId::Die
                             | AnimationId::BulletImpact => EndControl::Stay,
@@ -163,11 +165,15 @@ impl<'s> System<'s> for MarineAnimationSystem {
         )
             .join()
         {
-            let new_animation_id = match marine.state {
-                MarineState::Jumping => AnimationId::Jump,
-                MarineState::Running => AnimationId::Move,
-                MarineState::Shooting => AnimationId::Shoot,
-                MarineState::Dying => AnimationId::Die,
+            let is_shooting = marine.action != MarineAction::None;
+            let new_animation_id = match (marine.state, is_shooting) {
+                (MarineState::Dying, _) => AnimationId::Die,
+                (MarineState::Hurt, _) => AnimationId::Jump,
+                (MarineState::Jumping, false) => AnimationId::Jump,
+                (MarineState::Jumping, true) => AnimationId::JumpShoot,
+                (MarineState::Running, false) => AnimationId::Move,
+                (MarineState::Running, true) => AnimationId::RunShoot,
+                (_, true) => AnimationId::Shoot,
                 _ => AnimationId::Idle,
             };
 
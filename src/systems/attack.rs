@@ -1,75 +1,209 @@
+use std::{collections::VecDeque, mem};
+
 use amethyst::{
-    ecs::{Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, WriteStorage},
-    input::{InputHandler, StringBindings},
+    core::math::Vector2,
+    ecs::{
+        prelude::World, Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System,
+        SystemData, WriteStorage,
+    },
+    input::{InputEvent, InputHandler, StringBindings},
+    shrev::{EventChannel, ReaderId},
 };
 
 use crate::{
-    components::{Collider, Direction, Directions, Marine, MarineState, Motion},
-    entities::spawn_bullet,
-    resources::{AssetType, Context, SpriteSheetList},
+    components::{
+        Collider, Direction, Directions, Faction, Marine, MarineAction, MarineState, Weapon,
+    },
+    entities::{spawn_bullet, Shot},
+    resources::{Context, SpriteSheetList, STEP_SECONDS},
 };
 
-pub struct AttackSystem;
+/// How long the marine holds the shooting pose after firing, in seconds. Matches the length of
+/// the shooting animations.
+const SHOOTING_POSE_DURATION: f32 = 0.2;
+
+/// Distance between the shoulder of the marine and the muzzle of its gun.
+const GUN_REACH: f32 = 48.;
 
+/// Returns where the shoulder holding the gun is in each pose, relative to the bottom center of
+/// the marine's bounding box, with the marine facing right.
+fn shoulder_offset(state: MarineState) -> Vector2<f32> {
+    match state {
+        // The gun is held lower while running and closer to the chest while jumping.
+        MarineState::Running => Vector2::new(16., 42.),
+        MarineState::Jumping => Vector2::new(8., 40.),
+        _ => Vector2::new(16., 48.),
+    }
+}
+
+/// Puts the weapon in hand away and takes out the next holstered one, or the previous one when
+/// going `backwards`. The ammo left in each weapon is kept.
+fn cycle_weapon(weapon: &mut Weapon, holstered: &mut VecDeque<Weapon>, backwards: bool) {
+    let next = if backwards {
+        holstered.pop_back()
+    } else {
+        holstered.pop_front()
+    };
+    if let Some(next) = next {
+        let mut previous = mem::replace(weapon, next);
+        previous.charge = 0.;
+        if backwards {
+            holstered.push_front(previous);
+        } else {
+            holstered.push_back(previous);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AttackSystem {
+    reader_id: Option<ReaderId<InputEvent<StringBindings>>>,
+}
+
+/// Fires the weapon in the marine's hands where it aims, as set up in `resources/weapons.ron`,
+/// and switches weapons when the `next_weapon` or `previous_weapon` keys are pressed.
+///
+/// Depending on the weapon, a shot goes off when the shoot key is pressed, as long as it's held
+/// down or when it's released after charging. Shooting is a layer of its own, the marine can
+/// shoot while standing, running or jumping but not while it's hurt or dying.
 impl<'s> System<'s> for AttackSystem {
     type SystemData = (
         Entities<'s>,
         ReadStorage<'s, Collider>,
         WriteStorage<'s, Marine>,
-        ReadStorage<'s, Motion>,
+        WriteStorage<'s, Weapon>,
         ReadStorage<'s, Direction>,
         ReadExpect<'s, SpriteSheetList>,
         ReadExpect<'s, LazyUpdate>,
         Read<'s, InputHandler<StringBindings>>,
+        Read<'s, EventChannel<InputEvent<StringBindings>>>,
         ReadExpect<'s, Context>,
     );
 
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<InputEvent<StringBindings>>>()
+                .register_reader(),
+        );
+    }
+
     fn run(&mut self, data: Self::SystemData) {
         let (
             entities,
             colliders,
             mut marines,
-            motions,
+            mut weapons,
             directions,
             sprite_sheet_list,
             lazy_update,
             input,
+            input_events,
             ctx,
         ) = data;
 
-        for (mut marine, _, collider, direction) in
-            (&mut marines, &motions, &colliders, &directions).join()
+        // Key presses are events, so that a quick tap isn't missed between two physics steps.
+        let mut weapon_changes = Vec::new();
+        for event in input_events.read(self.reader_id.as_mut().unwrap()) {
+            if let InputEvent::ActionPressed(action) = event {
+                match action.as_ref() {
+                    "next_weapon" => weapon_changes.push(false),
+                    "previous_weapon" => weapon_changes.push(true),
+                    _ => {}
+                }
+            }
+        }
+
+        for (entity, marine, weapon, collider, direction) in (
+            &entities,
+            &mut marines,
+            &mut weapons,
+            &colliders,
+            &directions,
+        )
+            .join()
         {
+            for &backwards in weapon_changes.iter() {
+                cycle_weapon(weapon, &mut marine.holstered, backwards);
+            }
+
             let shoot_input = input.action_is_down("shoot").expect("shoot action exists");
+            let was_trigger_held = marine.is_trigger_held;
+            marine.is_trigger_held = shoot_input;
 
-            // Currently shooting is possible only when marine is static
-            if marine.state == MarineState::Shooting && !marine.is_shooting {
-                marine.is_shooting = true;
+            marine.action = match marine.action {
+                MarineAction::Shooting { time_left } if time_left > STEP_SECONDS => {
+                    MarineAction::Shooting {
+                        time_left: time_left - STEP_SECONDS,
+                    }
+                }
+                _ => MarineAction::None,
+            };
+            weapon.cooldown = (weapon.cooldown - STEP_SECONDS).max(0.);
 
-                let mut shoot_start_position = 0.;
-                let bbox = &collider.bounding_box;
-                if direction.x == Directions::Left {
-                    shoot_start_position = bbox.position.x - 64.;
-                } else if direction.x == Directions::Right {
-                    shoot_start_position = bbox.position.x + 64.;
+            let definition = &weapon.definition;
+            let is_firing = if definition.charge_time > 0. {
+                let is_charged = weapon.charge >= definition.charge_time;
+                weapon.charge = if shoot_input {
+                    weapon.charge + STEP_SECONDS
+                } else {
+                    0.
+                };
+                was_trigger_held && !shoot_input && is_charged
+            } else if definition.auto_fire {
+                shoot_input
+            } else {
+                shoot_input && !was_trigger_held
+            };
+
+            let can_shoot = !matches!(marine.state, MarineState::Hurt | MarineState::Dying);
+            if !is_firing || !can_shoot || !weapon.is_ready() {
+                continue;
+            }
+            let sprite_sheet_handle = match sprite_sheet_list.get(weapon.projectile.sprite_sheet) {
+                Some(handle) => handle.clone(),
+                None => {
+                    warn!(
+                        "No sprite sheet for the projectiles of the {} weapon",
+                        definition.name
+                    );
+                    continue;
                 }
+            };
+
+            weapon.spend_shot();
+            let (definition, projectile) = (&weapon.definition, &weapon.projectile);
+            marine.action = MarineAction::Shooting {
+                time_left: SHOOTING_POSE_DURATION,
+            };
 
-                let bullet_sprite_sheet_handle =
-                    { sprite_sheet_list.get(AssetType::Bullet).unwrap().clone() };
+            let bbox = &collider.bounding_box;
+            let offset = shoulder_offset(marine.state);
+            let shoulder = Vector2::new(
+                match direction.x {
+                    Directions::Left => bbox.position.x - offset.x,
+                    _ => bbox.position.x + offset.x,
+                },
+                bbox.position.y - bbox.half_size.y + offset.y,
+            );
+            let muzzle = shoulder + marine.aim * GUN_REACH;
+
+            for heading in definition.burst_headings(marine.aim) {
                 spawn_bullet(
                     &entities,
-                    bullet_sprite_sheet_handle,
-                    shoot_start_position,
-                    direction,
-                    bbox.position.y - bbox.half_size.y,
+                    sprite_sheet_handle.clone(),
+                    Shot {
+                        muzzle,
+                        heading,
+                        projectile,
+                        faction: Faction::Player,
+                        owner: entity,
+                    },
                     &lazy_update,
                     &ctx,
                 );
             }
-
-            if !shoot_input {
-                marine.is_shooting = false;
-            }
         }
     }
 }
@@ -0,0 +1,622 @@
+use std::{collections::HashMap, f32::consts::PI};
+
+use amethyst::{
+    core::math::Vector2,
+    ecs::{
+        Entities, Entity, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, WriteStorage,
+    },
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use crate::{
+    components::{
+        Behavior, Boundary, Collider, Direction, Directions, Faction, Health, Motion, NodeMemory,
+        Perception, Weapon,
+    },
+    entities::{spawn_bullet, Shot},
+    resources::{
+        Action, AssetType, BehaviorLibrary, BehaviorNode, Condition, Context, GameplayConfig,
+        LinkKind, NavGraph, SpriteSheetList, Status, STEP_SECONDS,
+    },
+};
+
+/// What an enemy knows of the target its `Perception` tracks.
+struct Target {
+    position: Vector2<f32>,
+    /// Bottom middle of its bounding box.
+    feet: Vector2<f32>,
+    on_ground: bool,
+    in_sight: bool,
+}
+
+/// An enemy as seen by its behavior tree, along with the outcome of the actions taken during
+/// the step.
+struct Agent<'a> {
+    position: Vector2<f32>,
+    half_size: Vector2<f32>,
+    on_ground: bool,
+    boundary: Option<&'a Boundary>,
+    home: Vector2<f32>,
+    /// Fraction of its hit points the enemy has left.
+    health: f32,
+    speed: f32,
+    gravity: f32,
+    nav_graph: &'a NavGraph,
+    /// Sign of the way the enemy faces along the x axis.
+    heading: f32,
+    target: Option<Target>,
+    weapon_ready: bool,
+    velocity: Vector2<f32>,
+    /// Sign of the way the enemy turns to, when it isn't the way it moves.
+    facing: Option<f32>,
+    /// Unit vector the enemy fires along.
+    shot: Option<Vector2<f32>>,
+    previous_memory: &'a HashMap<usize, NodeMemory>,
+    memory: HashMap<usize, NodeMemory>,
+    cooldowns: &'a mut HashMap<usize, f32>,
+}
+
+impl<'a> Agent<'a> {
+    fn is_at_left(&self) -> bool {
+        self.boundary.map_or(false, |boundary| {
+            self.position.x - self.half_size.x <= boundary.left
+        })
+    }
+
+    fn is_at_right(&self) -> bool {
+        self.boundary.map_or(false, |boundary| {
+            self.position.x + self.half_size.x >= boundary.right
+        })
+    }
+
+    fn is_at_top(&self) -> bool {
+        self.boundary.map_or(false, |boundary| {
+            self.position.y + self.half_size.y >= boundary.top
+        })
+    }
+
+    fn is_at_bottom(&self) -> bool {
+        self.boundary.map_or(false, |boundary| {
+            self.position.y - self.half_size.y <= boundary.bottom
+        })
+    }
+
+    fn to_target(&self) -> Option<Vector2<f32>> {
+        self.target
+            .as_ref()
+            .map(|target| target.position - self.position)
+    }
+
+    /// Ticks the `node`, numbered `index` in its tree.
+    fn tick(&mut self, node: &BehaviorNode, index: usize) -> Status {
+        match node {
+            BehaviorNode::Sequence(children) => {
+                let mut child_index = index + 1;
+                for child in children {
+                    let status = self.tick(child, child_index);
+                    if status != Status::Success {
+                        return status;
+                    }
+                    child_index += child.size();
+                }
+                Status::Success
+            }
+            BehaviorNode::Steps(children) => {
+                let first_step = match self.previous_memory.get(&index) {
+                    Some(NodeMemory::Step(step)) => *step,
+                    _ => 0,
+                };
+                let mut child_index = index
+                    + 1
+                    + children[..first_step]
+                        .iter()
+                        .map(BehaviorNode::size)
+                        .sum::<usize>();
+                for (step, child) in children.iter().enumerate().skip(first_step) {
+                    match self.tick(child, child_index) {
+                        Status::Success => child_index += child.size(),
+                        Status::Running => {
+                            self.memory.insert(index, NodeMemory::Step(step));
+                            return Status::Running;
+                        }
+                        Status::Failure => return Status::Failure,
+                    }
+                }
+                Status::Success
+            }
+            BehaviorNode::Selector(children) => {
+                let mut child_index = index + 1;
+                for child in children {
+                    let status = self.tick(child, child_index);
+                    if status != Status::Failure {
+                        return status;
+                    }
+                    child_index += child.size();
+                }
+                Status::Failure
+            }
+            BehaviorNode::Not(child) => match self.tick(child, index + 1) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            BehaviorNode::Cooldown(duration, child) => {
+                if self.cooldowns.contains_key(&index) {
+                    return Status::Failure;
+                }
+                let status = self.tick(child, index + 1);
+                if status == Status::Success {
+                    self.cooldowns.insert(index, *duration);
+                }
+                status
+            }
+            BehaviorNode::Condition(condition) => {
+                if self.check(*condition) {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            BehaviorNode::Action(action) => self.act(*action, index),
+        }
+    }
+
+    fn check(&self, condition: Condition) -> bool {
+        let to_target = self.to_target();
+        match condition {
+            Condition::HasTarget => self.target.is_some(),
+            Condition::TargetInSight => {
+                self.target.as_ref().map_or(false, |target| target.in_sight)
+            }
+            Condition::TargetWithin(distance) => {
+                to_target.map_or(false, |to_target| to_target.norm() <= distance)
+            }
+            Condition::TargetHorizontallyWithin(distance) => {
+                to_target.map_or(false, |to_target| to_target.x.abs() <= distance)
+            }
+            Condition::TargetAbove => to_target.map_or(false, |to_target| to_target.y > 0.),
+            Condition::TargetBelow => to_target.map_or(false, |to_target| to_target.y < 0.),
+            Condition::TargetGrounded => self
+                .target
+                .as_ref()
+                .map_or(false, |target| target.on_ground),
+            Condition::HealthBelow(fraction) => self.health < fraction,
+            Condition::OnGround => self.on_ground,
+        }
+    }
+
+    fn act(&mut self, action: Action, index: usize) -> Status {
+        let to_target = self.to_target();
+        match action {
+            Action::Patrol => {
+                let mut heading = if self.velocity.x != 0. {
+                    self.velocity.x.signum()
+                } else {
+                    self.heading
+                };
+                if self.is_at_left() {
+                    heading = 1.;
+                } else if self.is_at_right() {
+                    heading = -1.;
+                }
+                self.velocity.x = heading * self.speed;
+                Status::Running
+            }
+            Action::Chase => match to_target {
+                Some(to_target) => {
+                    if to_target.x.abs() > std::f32::EPSILON {
+                        self.velocity.x = to_target.x.signum() * self.speed;
+                    }
+                    Status::Running
+                }
+                None => Status::Failure,
+            },
+            Action::Pursue => self.pursue(),
+            Action::Flee => match to_target {
+                Some(to_target) => {
+                    let heading = if to_target.x > 0. { -1. } else { 1. };
+                    let is_cornered = if heading < 0. {
+                        self.is_at_left()
+                    } else {
+                        self.is_at_right()
+                    };
+                    self.velocity.x = if is_cornered {
+                        0.
+                    } else {
+                        heading * self.speed
+                    };
+                    Status::Running
+                }
+                None => Status::Failure,
+            },
+            Action::Wait(duration) => {
+                self.velocity.x = 0.;
+                let waited = match self.previous_memory.get(&index) {
+                    Some(NodeMemory::Waited(waited)) => *waited,
+                    _ => 0.,
+                } + STEP_SECONDS;
+                if waited >= duration {
+                    Status::Success
+                } else {
+                    self.memory.insert(index, NodeMemory::Waited(waited));
+                    Status::Running
+                }
+            }
+            Action::Face => match to_target {
+                Some(to_target) => {
+                    if to_target.x.abs() > std::f32::EPSILON {
+                        self.facing = Some(to_target.x.signum());
+                    }
+                    Status::Success
+                }
+                None => Status::Failure,
+            },
+            Action::Shoot => {
+                let to_target = match &self.target {
+                    Some(target) if target.in_sight && self.weapon_ready => {
+                        target.position - self.position
+                    }
+                    _ => return Status::Failure,
+                };
+                let distance = to_target.norm();
+                if distance <= std::f32::EPSILON {
+                    return Status::Failure;
+                }
+                let aim = to_target / distance;
+                if aim.x != 0. {
+                    self.facing = Some(aim.x.signum());
+                }
+                self.shot = Some(aim);
+                Status::Success
+            }
+            Action::Jump(velocity) => {
+                if self.on_ground {
+                    self.velocity.y = velocity;
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            Action::Swoop(speed) => {
+                let (heading, floor) = match (self.previous_memory.get(&index), to_target) {
+                    (Some(NodeMemory::Swoop { heading, floor }), _) => (*heading, *floor),
+                    (_, Some(to_target)) if to_target.norm() > std::f32::EPSILON => {
+                        (to_target.normalize(), self.position.y + to_target.y)
+                    }
+                    _ => return Status::Failure,
+                };
+                if self.position.y <= floor || self.on_ground || self.is_at_bottom() {
+                    if self.velocity.x != 0. {
+                        self.velocity.x = self.velocity.x.signum() * self.speed;
+                    }
+                    self.velocity.y = 0.;
+                    return Status::Success;
+                }
+                self.velocity = heading * speed;
+                self.memory
+                    .insert(index, NodeMemory::Swoop { heading, floor });
+                Status::Running
+            }
+            Action::Climb(speed) => {
+                if self.velocity.x != 0. {
+                    self.velocity.x = self.velocity.x.signum() * self.speed;
+                }
+                if self.position.y >= self.home.y || self.is_at_top() {
+                    Status::Success
+                } else {
+                    self.velocity.y = speed;
+                    Status::Running
+                }
+            }
+        }
+    }
+
+    /// Walks toward the first link of the way to the target, and takes it once there.
+    fn pursue(&mut self) -> Status {
+        let target_feet = match &self.target {
+            Some(target) if self.gravity > 0. => target.feet,
+            _ => return Status::Failure,
+        };
+        if !self.on_ground {
+            // Keeps going until it lands.
+            return Status::Running;
+        }
+        let feet = self.position - Vector2::new(0., self.half_size.y);
+        let path = match self.nav_graph.find_path(feet, target_feet) {
+            Some(path) => path,
+            None => return Status::Failure,
+        };
+        let link = match path.first() {
+            Some(link) => link,
+            None => {
+                // Already on the target's surface.
+                if (target_feet.x - feet.x).abs() > std::f32::EPSILON {
+                    self.velocity.x = (target_feet.x - feet.x).signum() * self.speed;
+                }
+                return Status::Running;
+            }
+        };
+        let leaves_boundary = self.boundary.map_or(false, |boundary| {
+            link.take_off.x < boundary.left || link.take_off.x > boundary.right
+        });
+        if leaves_boundary {
+            return Status::Failure;
+        }
+
+        let to_take_off = link.take_off.x - feet.x;
+        if to_take_off.abs() > self.speed * STEP_SECONDS {
+            self.velocity.x = to_take_off.signum() * self.speed;
+        } else {
+            self.velocity.x = (link.landing.x - link.take_off.x).signum() * self.speed;
+            if link.kind == LinkKind::Jump {
+                self.velocity.y = link.jump_velocity(self.speed, self.gravity);
+            }
+        }
+        Status::Running
+    }
+}
+
+/// Returns how an enemy of the given `kind` at `position` steers away from the others of its
+/// kind closer than `radius`: the sum of the unit vectors pointing away from each of them,
+/// weighted by how close it is.
+fn separation(
+    entity: Entity,
+    kind: AssetType,
+    position: Vector2<f32>,
+    radius: f32,
+    enemies: &[(Entity, AssetType, Vector2<f32>)],
+) -> Vector2<f32> {
+    enemies
+        .iter()
+        .filter(|(other, other_kind, _)| *other != entity && *other_kind == kind)
+        .fold(Vector2::new(0., 0.), |steering, (_, _, other_position)| {
+            let away = position - other_position;
+            let distance = away.norm();
+            if distance >= radius || distance <= std::f32::EPSILON {
+                steering
+            } else {
+                steering + away / distance * (1. - distance / radius)
+            }
+        })
+}
+
+pub struct BehaviorSystem;
+
+/// Ticks the behavior tree of every enemy, from its root, once per physics step.
+///
+/// Before the tree runs, the enemy falls with the gravity of its tree, or follows the hovering
+/// waves of a flying one. The actions then set its velocity, turn it around and fire its
+/// `Weapon`, while the conditions look at its `Perception`, its health and its surroundings.
+/// The nodes still running at the end of a step remember where they're at, the others start
+/// over the next time they're ticked.
+///
+/// Enemies tuned with a separation radius then steer away from the others of their kind, on top
+/// of the velocity set by the actions, only sideways unless they hover.
+///
+/// The enemy never leaves the vertical bounds of its `Boundary`, the horizontal ones being
+/// handled by the collisions.
+///
+/// Finally, this system gives the enemies a red tint while they have a target, and no tint
+/// otherwise.
+impl<'s> System<'s> for BehaviorSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Behavior>,
+        WriteStorage<'s, Perception>,
+        WriteStorage<'s, Motion>,
+        WriteStorage<'s, Direction>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, Tint>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Boundary>,
+        ReadStorage<'s, Health>,
+        Read<'s, GameplayConfig>,
+        Read<'s, NavGraph>,
+        ReadExpect<'s, BehaviorLibrary>,
+        ReadExpect<'s, SpriteSheetList>,
+        ReadExpect<'s, LazyUpdate>,
+        ReadExpect<'s, Context>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut behaviors,
+            mut perceptions,
+            mut motions,
+            mut directions,
+            mut weapons,
+            mut tints,
+            colliders,
+            boundaries,
+            healths,
+            config,
+            nav_graph,
+            library,
+            sprite_sheet_list,
+            lazy_update,
+            ctx,
+        ) = data;
+
+        let enemies: Vec<(Entity, AssetType, Vector2<f32>)> = (&entities, &behaviors, &colliders)
+            .join()
+            .map(|(entity, behavior, collider)| {
+                (entity, behavior.kind, collider.bounding_box.position)
+            })
+            .collect();
+
+        for (
+            entity,
+            behavior,
+            perception,
+            motion,
+            direction,
+            collider,
+            boundary,
+            health,
+            mut weapon,
+            tint,
+        ) in (
+            &entities,
+            &mut behaviors,
+            &mut perceptions,
+            &mut motions,
+            &mut directions,
+            &colliders,
+            (&boundaries).maybe(),
+            (&healths).maybe(),
+            (&mut weapons).maybe(),
+            (&mut tints).maybe(),
+        )
+            .join()
+        {
+            let (tree, enemy_config) =
+                match (library.get(&behavior.tree), config.enemy(behavior.kind)) {
+                    (Some(tree), Some(enemy_config)) => (tree, enemy_config),
+                    _ => continue,
+                };
+            // The senses may have been tuned since the enemy spawned.
+            perception.config = enemy_config.perception;
+            if let Some(weapon) = &mut weapon {
+                weapon.cooldown = (weapon.cooldown - STEP_SECONDS).max(0.);
+            }
+            for cooldown in behavior.cooldowns.values_mut() {
+                *cooldown -= STEP_SECONDS;
+            }
+            behavior.cooldowns.retain(|_, cooldown| *cooldown > 0.);
+
+            let bbox = &collider.bounding_box;
+            let position = bbox.position;
+            let mut velocity = motion.velocity - behavior.steering;
+            if let Some(hover) = tree.hover {
+                // Follows the hovering sine wave, no faster than it could climb or dive.
+                behavior.hover_phase =
+                    (behavior.hover_phase + 2. * PI * hover.frequency * STEP_SECONDS) % (2. * PI);
+                let hover_y = behavior.home.y + hover.amplitude * behavior.hover_phase.sin();
+                velocity.y = ((hover_y - position.y) / STEP_SECONDS)
+                    .max(-hover.speed)
+                    .min(hover.speed);
+            } else {
+                velocity.y -= tree.gravity * STEP_SECONDS;
+            }
+
+            let target = perception
+                .target
+                .and_then(|target| colliders.get(target))
+                .map(|target_collider| Target {
+                    position: target_collider.bounding_box.position,
+                    feet: target_collider.bounding_box.position
+                        - Vector2::new(0., target_collider.bounding_box.half_size.y),
+                    on_ground: target_collider.on_ground,
+                    in_sight: perception.sees_target,
+                });
+            let mut agent = Agent {
+                position,
+                half_size: bbox.half_size,
+                on_ground: collider.on_ground,
+                boundary,
+                home: behavior.home,
+                health: health.map_or(1., |health| {
+                    health.current as f32 / health.max.max(1) as f32
+                }),
+                speed: enemy_config.speed,
+                gravity: tree.gravity,
+                nav_graph: &nav_graph,
+                heading: if direction.x == Directions::Right {
+                    1.
+                } else {
+                    -1.
+                },
+                target,
+                weapon_ready: weapon.as_ref().map_or(false, |weapon| weapon.is_ready()),
+                velocity,
+                facing: None,
+                shot: None,
+                previous_memory: &behavior.memory,
+                memory: HashMap::new(),
+                cooldowns: &mut behavior.cooldowns,
+            };
+            agent.tick(&tree.root, 0);
+
+            let Agent {
+                mut velocity,
+                facing,
+                shot,
+                memory,
+                ..
+            } = agent;
+            behavior.memory = memory;
+
+            // Steers on top of what the actions asked for, without becoming part of it.
+            let mut steering = Vector2::new(0., 0.);
+            if enemy_config.separation_radius > 0. {
+                steering = separation(
+                    entity,
+                    behavior.kind,
+                    position,
+                    enemy_config.separation_radius,
+                    &enemies,
+                ) * enemy_config.separation_speed;
+                if tree.hover.is_none() {
+                    steering.y = 0.;
+                }
+            }
+            behavior.steering = steering;
+            velocity += steering;
+
+            // Stay within the vertical bounds.
+            if let Some(boundary) = boundary {
+                if (velocity.y > 0. && position.y + bbox.half_size.y >= boundary.top)
+                    || (velocity.y < 0. && position.y - bbox.half_size.y <= boundary.bottom)
+                {
+                    velocity.y = 0.;
+                }
+            }
+            motion.velocity = velocity;
+            if let Some(facing) = facing {
+                direction.set_x_velocity(facing);
+            } else if velocity.x != 0. {
+                direction.set_x_velocity(velocity.x);
+            }
+
+            // Give enemy a red tint if they are after a target, or no tint if they're not.
+            if let Some(tint) = tint {
+                tint.0 = match perception.target {
+                    Some(_) => Srgba::new(1.0, 0.0, 0.0, 1.0),
+                    None => Srgba::new(1.0, 1.0, 1.0, 1.0),
+                };
+            }
+
+            if let (Some(aim), Some(weapon)) = (shot, weapon) {
+                let sprite_sheet_handle =
+                    match sprite_sheet_list.get(weapon.projectile.sprite_sheet) {
+                        Some(handle) => handle.clone(),
+                        None => {
+                            warn!(
+                                "No sprite sheet for the projectiles of the {} weapon",
+                                weapon.definition.name
+                            );
+                            continue;
+                        }
+                    };
+                weapon.spend_shot();
+                let (definition, projectile) = (&weapon.definition, &weapon.projectile);
+                for heading in definition.burst_headings(aim) {
+                    spawn_bullet(
+                        &entities,
+                        sprite_sheet_handle.clone(),
+                        Shot {
+                            muzzle: position,
+                            heading,
+                            projectile,
+                            faction: Faction::Enemy,
+                            owner: entity,
+                        },
+                        &lazy_update,
+                        &ctx,
+                    );
+                }
+            }
+        }
+    }
+}
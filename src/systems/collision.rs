@@ -1,19 +1,33 @@
 use amethyst::{
-    core::{math::Vector2, Named, Transform},
-    ecs::{Entities, Join, LazyUpdate, ReadExpect, ReadStorage, System, WriteStorage},
+    core::math::Vector2,
+    ecs::{
+        prelude::World, Entities, Entity, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System,
+        SystemData, Write, WriteStorage,
+    },
+    shrev::{EventChannel, ReaderId},
 };
 
+use super::health::INVULNERABILITY_DURATION;
 use crate::{
     components::{
-        Boundary, Bullet, Collidee, CollideeDetails, Collider, Direction, Directions, Flier,
-        FlierAi, Marine, Motion, Pincer, PincerAi,
+        Behavior, Boundary, Bullet, Collidee, CollideeDetails, Collider, Damage, Direction,
+        Faction, Health, Invulnerability, Marine, Motion, Owner, Perception,
+    },
+    entities::show_bullet_impact,
+    resources::{
+        AssetType, CollisionAxis, CollisionEvent, Context, DeathEvent, PrefabList, SpatialGrid,
     },
-    entities::{show_bullet_impact, show_explosion},
-    resources::{AssetType, Context, PrefabList},
 };
 
 pub struct CollisionSystem;
 
+/// Finds the contacts of the moving entities, with each other and with their boundaries.
+///
+/// Only the colliders sharing a cell of the `SpatialGrid` are tested against each other.
+/// Continuous colliders are swept from their old position, and only their first contact is
+/// reported.
+/// The corrections needed to resolve them are kept in the `Collidee` of each entity, for the
+/// `TransformationSystem`, while every contact is published as a `CollisionEvent`.
 impl<'s> System<'s> for CollisionSystem {
     type SystemData = (
         Entities<'s>,
@@ -21,11 +35,32 @@ impl<'s> System<'s> for CollisionSystem {
         WriteStorage<'s, Collidee>,
         ReadStorage<'s, Boundary>,
         ReadStorage<'s, Motion>,
-        ReadStorage<'s, Named>,
+        Write<'s, SpatialGrid>,
+        Write<'s, EventChannel<CollisionEvent>>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, colliders, mut collidees, boundaries, motions, names) = data;
+        let (
+            entities,
+            colliders,
+            mut collidees,
+            boundaries,
+            motions,
+            mut grid,
+            mut collision_events,
+        ) = data;
+
+        // The static colliders were inserted when the map was loaded, only the moving ones
+        // have to be bucketed again.
+        grid.clear_dynamic();
+        for (entity, collider, _) in (&entities, &colliders, &motions).join() {
+            if !grid.is_static(entity) {
+                let (min, max) = collider.bounds();
+                grid.insert_dynamic(entity, min, max);
+            }
+        }
+
+        let mut candidates = Vec::new();
 
         for (entity_a, collider_a, collidee, boundary, motion_a) in
             (&entities, &colliders, &mut collidees, &boundaries, &motions).join()
@@ -37,25 +72,57 @@ impl<'s> System<'s> for CollisionSystem {
             let correction;
 
             if velocity_a.x != 0. || velocity_a.y != 0. && collider_a.is_collidable {
-                for (entity_b, collider_b, motion_b, name_b) in
-                    (&entities, &colliders, &motions, &names).join()
-                {
+                let (min, max) = collider_a.bounds();
+                grid.query(min, max, &mut candidates);
+
+                // Continuous colliders only report the first thing they touch on their way.
+                let mut first_impact = None;
+
+                for &entity_b in candidates.iter() {
+                    let (collider_b, motion_b) =
+                        match (colliders.get(entity_b), motions.get(entity_b)) {
+                            (Some(collider_b), Some(motion_b)) => (collider_b, motion_b),
+                            _ => continue,
+                        };
+                    if entity_a == entity_b || !collider_a.can_collide_with(collider_b) {
+                        continue;
+                    }
+                    if collider_a.is_continuous {
+                        if let Some((time_of_impact, normal)) =
+                            bbox_a.sweep(&collider_b.bounding_box)
+                        {
+                            match first_impact {
+                                Some((first_time, _, _)) if first_time <= time_of_impact => {}
+                                _ => first_impact = Some((time_of_impact, normal, entity_b)),
+                            }
+                        }
+                        continue;
+                    }
                     let velocity_b = motion_b.velocity;
                     let use_hit_box =
                         (velocity_a.x * velocity_b.x != 0.) || (velocity_a.y * velocity_b.y != 0.);
-                    if entity_a != entity_b
-                        && collider_a.is_overlapping_with(collider_b, use_hit_box)
-                    {
-                        collidee.set_collidee_details(
-                            name_b.name.to_string(),
+                    if collider_a.is_overlapping_with(collider_b, use_hit_box) {
+                        collision_events.single_write(collidee.set_collidee_details(
+                            entity_a,
+                            entity_b,
                             collider_a,
                             collider_b,
                             velocity_a,
                             velocity_b,
                             use_hit_box,
-                        );
+                        ));
                     }
                 }
+
+                if let Some((time_of_impact, normal, entity_b)) = first_impact {
+                    collision_events.single_write(collidee.set_swept_collidee_details(
+                        entity_a,
+                        entity_b,
+                        collider_a,
+                        time_of_impact,
+                        normal,
+                    ));
+                }
             }
 
             correction = if (position_a_x - half_size_a_x) <= boundary.left {
@@ -67,275 +134,288 @@ impl<'s> System<'s> for CollisionSystem {
             };
 
             if correction != 0. {
-                collidee.horizontal = Some(CollideeDetails {
-                    name: String::from("Boundary"),
-                    position: Vector2::new(0., 0.),
-                    half_size: Vector2::new(0., 0.),
-                    correction,
+                collidee.horizontal = Some(CollideeDetails { correction });
+                collision_events.single_write(CollisionEvent {
+                    entity: entity_a,
+                    other: None,
+                    axis: CollisionAxis::Horizontal,
+                    penetration: correction.abs(),
+                    normal: Vector2::new(-correction.signum(), 0.),
+                    contact_position: Vector2::new(position_a_x - correction, bbox_a.position.y),
                 });
             }
         }
     }
 }
 
-pub struct PincerCollisionSystem;
+#[derive(Default)]
+pub struct EnemyCollisionSystem {
+    reader_id: Option<ReaderId<CollisionEvent>>,
+}
 
-impl<'s> System<'s> for PincerCollisionSystem {
+/// Turns enemies bumping into each other around, and sends a shot enemy after the shooter.
+impl<'s> System<'s> for EnemyCollisionSystem {
     type SystemData = (
         Entities<'s>,
-        ReadStorage<'s, Marine>,
-        WriteStorage<'s, Pincer>,
-        ReadStorage<'s, Collidee>,
+        ReadStorage<'s, Bullet>,
+        ReadStorage<'s, Owner>,
+        ReadStorage<'s, Behavior>,
+        ReadStorage<'s, Collider>,
+        WriteStorage<'s, Perception>,
         WriteStorage<'s, Direction>,
         WriteStorage<'s, Motion>,
-        ReadExpect<'s, PrefabList>,
-        ReadStorage<'s, Transform>,
-        ReadExpect<'s, LazyUpdate>,
-        ReadExpect<'s, Context>,
+        Read<'s, EventChannel<CollisionEvent>>,
     );
 
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CollisionEvent>>()
+                .register_reader(),
+        );
+    }
+
     fn run(&mut self, data: Self::SystemData) {
         let (
             entities,
-            marines,
-            mut pincers,
-            collidees,
+            bullets,
+            owners,
+            behaviors,
+            colliders,
+            mut perceptions,
             mut dirs,
             mut motions,
-            prefab_list,
-            transforms,
-            lazy_update,
-            ctx,
+            collision_events,
         ) = data;
 
-        // We need to set a target for the pincer to attack.
-        // For now, assume there is only one marine and if the pincer gets shot that marine is
-        // always the one who did it.
-        // Perhaps, later, the shooter could be attached to the bullet/collider entity somehow.
-        let marine_opt = (&entities, &marines)
-            .join()
-            .map(|(entity, _)| entity)
-            .next();
-
-        for (entity, pincer, collidee, dir, motion, transform) in (
-            &*entities,
-            &mut pincers,
-            &collidees,
-            &mut dirs,
-            &mut motions,
-            &transforms,
-        )
-            .join()
-        {
-            if let Some(collidee_horizontal) = &collidee.horizontal {
-                match collidee_horizontal.name.as_ref() {
-                    "Boundary" => {
-                        pincer.ai = PincerAi::Patrolling;
-                        motion.velocity.x *= -1.;
-                        dir.set_x_velocity(motion.velocity.x);
-                    }
-                    "Bullet" => {
-                        if let Some(marine) = marine_opt {
-                            pincer.ai = PincerAi::Attacking { target: marine };
-                        }
-                        pincer.hit_count += 1;
-                        if pincer.hit_count == 4 {
-                            let small_explosion_prefab_handle =
-                                { prefab_list.get(AssetType::SmallExplosion).unwrap().clone() };
-                            let pincer_translation = transform.translation();
-                            show_explosion(
-                                &entities,
-                                small_explosion_prefab_handle,
-                                pincer_translation.x,
-                                pincer_translation.y,
-                                &lazy_update,
-                                &ctx,
-                            );
-                            let _ = entities.delete(entity);
-                        }
+        for event in collision_events.read(self.reader_id.as_mut().unwrap()) {
+            let other = match event.other {
+                Some(other) => other,
+                None => continue,
+            };
+            // The enemy ran into another one, both turn around unless the other one passes
+            // through the enemies.
+            if behaviors.contains(event.entity) && behaviors.contains(other) {
+                if event.axis != CollisionAxis::Horizontal {
+                    continue;
+                }
+                let other_bumps = match (colliders.get(other), colliders.get(event.entity)) {
+                    (Some(other_collider), Some(collider)) => {
+                        other_collider.can_collide_with(collider)
                     }
-                    _ => {}
+                    _ => false,
+                };
+                turn_away(event.entity, event.normal.x, &mut dirs, &mut motions);
+                if other_bumps {
+                    turn_away(other, -event.normal.x, &mut dirs, &mut motions);
+                }
+                continue;
+            }
+
+            let is_shot_by_player = bullets
+                .get(event.entity)
+                .map_or(false, |bullet| bullet.faction == Faction::Player);
+            if !is_shot_by_player {
+                continue;
+            }
+            // Go after whoever fired the bullet, as long as it's still around.
+            if let (Some(perception), Some(owner)) =
+                (perceptions.get_mut(other), owners.get(event.entity))
+            {
+                if entities.is_alive(owner.entity) {
+                    perception.alert(owner.entity);
                 }
             }
         }
     }
 }
 
-pub struct FlierCollisionSystem;
-
-impl<'s> System<'s> for FlierCollisionSystem {
-    type SystemData = (
-        Entities<'s>,
-        ReadStorage<'s, Marine>,
-        WriteStorage<'s, Flier>,
-        ReadStorage<'s, Collidee>,
-        WriteStorage<'s, Direction>,
-        WriteStorage<'s, Motion>,
-        ReadExpect<'s, PrefabList>,
-        ReadStorage<'s, Transform>,
-        ReadExpect<'s, LazyUpdate>,
-        ReadExpect<'s, Context>,
-    );
-
-    fn run(&mut self, data: Self::SystemData) {
-        let (
-            entities,
-            marines,
-            mut fliers,
-            collidees,
-            mut dirs,
-            mut motions,
-            prefab_list,
-            transforms,
-            lazy_update,
-            ctx,
-        ) = data;
-
-        // We need to set a target for the pincer to attack.
-        // For now, assume there is only one marine and if the pincer gets shot that marine is
-        // always the one who did it.
-        // Perhaps, later, the shooter could be attached to the bullet/collider entity somehow.
-        let marine_opt = (&entities, &marines)
-            .join()
-            .map(|(entity, _)| entity)
-            .next();
-
-        for (entity, flier, collidee, dir, motion, transform) in (
-            &*entities,
-            &mut fliers,
-            &collidees,
-            &mut dirs,
-            &mut motions,
-            &transforms,
-        )
-            .join()
-        {
-            if let Some(collidee_horizontal) = &collidee.horizontal {
-                match collidee_horizontal.name.as_ref() {
-                    // TODO: Enemies might collide with each other... what to do about that
-                    "Boundary" => {
-                        flier.ai = FlierAi::Patrolling;
-                        motion.velocity.x *= -1.;
-                        dir.set_x_velocity(motion.velocity.x);
-                    }
-                    "Bullet" => {
-                        if let Some(marine) = marine_opt {
-                            flier.ai = FlierAi::Attacking { target: marine };
-                        }
-                        flier.hit_count += 1;
-                        // FIXME: enemy max hit points should be stored alongside entity
-                        if flier.hit_count == 6 {
-                            let small_explosion_prefab_handle =
-                                { prefab_list.get(AssetType::SmallExplosion).unwrap().clone() };
-                            let flier_translation = transform.translation();
-                            show_explosion(
-                                &entities,
-                                small_explosion_prefab_handle,
-                                flier_translation.x,
-                                flier_translation.y,
-                                &lazy_update,
-                                &ctx,
-                            );
-                            let _ = entities.delete(entity);
-                        }
-                    }
-                    _ => {}
-                }
-            }
+/// Turns an enemy around when it heads the wrong way along `away`, the x of the normal pointing
+/// away from what it bumped into. Several contacts during the same step only turn it around
+/// once.
+fn turn_away(
+    entity: Entity,
+    away: f32,
+    dirs: &mut WriteStorage<Direction>,
+    motions: &mut WriteStorage<Motion>,
+) {
+    if let (Some(dir), Some(motion)) = (dirs.get_mut(entity), motions.get_mut(entity)) {
+        if motion.velocity.x * away < 0. {
+            motion.velocity.x *= -1.;
+            dir.set_x_velocity(motion.velocity.x);
         }
     }
 }
 
-pub struct BulletCollisionSystem;
+#[derive(Default)]
+pub struct BulletCollisionSystem {
+    reader_id: Option<ReaderId<CollisionEvent>>,
+}
 
 impl<'s> System<'s> for BulletCollisionSystem {
     type SystemData = (
         Entities<'s>,
         ReadStorage<'s, Bullet>,
         ReadStorage<'s, Collider>,
-        ReadStorage<'s, Collidee>,
-        WriteStorage<'s, Direction>,
-        WriteStorage<'s, Motion>,
+        ReadStorage<'s, Boundary>,
+        ReadStorage<'s, Motion>,
+        Read<'s, EventChannel<CollisionEvent>>,
         ReadExpect<'s, PrefabList>,
         ReadExpect<'s, LazyUpdate>,
         ReadExpect<'s, Context>,
     );
 
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CollisionEvent>>()
+                .register_reader(),
+        );
+    }
+
     fn run(&mut self, data: Self::SystemData) {
         let (
             entities,
             bullets,
             colliders,
-            collidees,
-            mut dirs,
-            mut motions,
+            boundaries,
+            motions,
+            collision_events,
             prefab_list,
             lazy_update,
             ctx,
         ) = data;
 
-        for (entity, _, collider, collidee, dir, motion) in (
-            &*entities,
-            &bullets,
-            &colliders,
-            &collidees,
-            &mut dirs,
-            &mut motions,
-        )
-            .join()
+        // A bullet stops at the first thing it hits, even if it touches several at once.
+        let mut stopped_bullets = Vec::new();
+
+        for event in collision_events.read(self.reader_id.as_mut().unwrap()) {
+            let entity = event.entity;
+            if !bullets.contains(entity) || stopped_bullets.contains(&entity) {
+                continue;
+            }
+            stopped_bullets.push(entity);
+
+            if let (Some(_), Some(collider), Some(motion)) =
+                (event.other, colliders.get(entity), motions.get(entity))
+            {
+                let bullet_impact_prefab_handle =
+                    { prefab_list.get(AssetType::BulletImpact).unwrap().clone() };
+                // The impact is shown where the front of the bullet touched the entity it hit,
+                // on the side of the bullet given by the axis of the collision.
+                let heading = match event.axis {
+                    CollisionAxis::Horizontal => Vector2::new(motion.velocity.x.signum(), 0.),
+                    CollisionAxis::Vertical => Vector2::new(0., motion.velocity.y.signum()),
+                };
+                let half_size = collider.bounding_box.half_size;
+                show_bullet_impact(
+                    &entities,
+                    bullet_impact_prefab_handle,
+                    event.contact_position + half_size.component_mul(&heading),
+                    heading,
+                    &lazy_update,
+                    &ctx,
+                );
+            }
+            let _ = entities.delete(entity);
+        }
+
+        // The level boundaries only stop the entities horizontally, the bullets going up or
+        // down are removed once they are out of the level.
+        for (entity, _, collider, boundary) in (&entities, &bullets, &colliders, &boundaries).join()
         {
-            // Currently, bullet can be fired only horizontally
-            if let Some(collidee_horizontal) = &collidee.horizontal {
-                match collidee_horizontal.name.as_ref() {
-                    "Boundary" => {}
-                    _ => {
-                        let bullet_impact_prefab_handle =
-                            { prefab_list.get(AssetType::BulletImpact).unwrap().clone() };
-                        let impact_position_x = match dir.x {
-                            Directions::Right => {
-                                collidee_horizontal.position.x - collidee_horizontal.half_size.x
-                            }
-                            Directions::Left => {
-                                collidee_horizontal.position.x + collidee_horizontal.half_size.x
-                            }
-                            _ => 0.,
-                        };
-                        show_bullet_impact(
-                            &entities,
-                            bullet_impact_prefab_handle,
-                            impact_position_x,
-                            collider.bounding_box.position.y,
-                            motion.velocity.x,
-                            &lazy_update,
-                            &ctx,
-                        );
-                    }
-                }
+            let position_y = collider.bounding_box.position.y;
+            if position_y > boundary.top || position_y < boundary.bottom {
                 let _ = entities.delete(entity);
             }
         }
     }
 }
 
-pub struct MarineCollisionSystem;
+/// Horizontal and vertical velocity given to the marine when an enemy touches it.
+const KNOCKBACK_VELOCITY: (f32, f32) = (300., 420.);
+
+/// Hurts the marine when it runs into an enemy, or the other way around, from any side.
+#[derive(Default)]
+pub struct MarineCollisionSystem {
+    reader_id: Option<ReaderId<CollisionEvent>>,
+}
 
 impl<'s> System<'s> for MarineCollisionSystem {
     type SystemData = (
-        ReadStorage<'s, Marine>,
-        WriteStorage<'s, Collider>,
-        ReadStorage<'s, Collidee>,
+        WriteStorage<'s, Marine>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Invulnerability>,
+        ReadStorage<'s, Damage>,
+        ReadStorage<'s, Bullet>,
+        Read<'s, EventChannel<CollisionEvent>>,
+        Write<'s, EventChannel<DeathEvent>>,
     );
 
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CollisionEvent>>()
+                .register_reader(),
+        );
+    }
+
     fn run(&mut self, data: Self::SystemData) {
-        let (marines, mut colliders, collidees) = data;
+        let (
+            mut marines,
+            mut healths,
+            mut invulnerabilities,
+            damages,
+            bullets,
+            collision_events,
+            mut death_events,
+        ) = data;
+
+        for event in collision_events.read(self.reader_id.as_mut().unwrap()) {
+            // The normal always points away from the other entity, flip it when the enemy is
+            // the one who ran into the marine.
+            let (entity, enemy, away) = match event.other {
+                Some(other) if marines.contains(event.entity) => {
+                    (event.entity, other, event.normal)
+                }
+                Some(other) if marines.contains(other) => (other, event.entity, -event.normal),
+                _ => continue,
+            };
+            // Projectiles are taken care of by the DamageSystem.
+            let damage = match damages.get(enemy) {
+                Some(damage) if !bullets.contains(enemy) => damage.amount,
+                _ => continue,
+            };
 
-        for (_, collider, collidee) in (&marines, &mut colliders, &collidees).join() {
-            if let Some(collidee_horizontal) = &collidee.horizontal {
-                if let "Pincer" = collidee_horizontal.name.as_ref() {
-                    collider.is_collidable = false;
+            if let (Some(marine), Some(health)) = (marines.get_mut(entity), healths.get_mut(entity))
+            {
+                if health.is_dead() || invulnerabilities.contains(entity) {
+                    continue;
                 }
-                if let "Flier" = collidee_horizontal.name.as_ref() {
-                    collider.is_collidable = false;
+                health.damage(damage);
+
+                if health.is_dead() {
+                    death_events.single_write(DeathEvent {
+                        entity,
+                        killer: Some(enemy),
+                    });
+                } else {
+                    // Throw the marine away from the enemy, along the normal of the contact. A
+                    // side hit also makes it hop.
+                    marine.is_knocked_back = true;
+                    marine.knockback = Some(Vector2::new(
+                        away.x * KNOCKBACK_VELOCITY.0,
+                        if away.y != 0. {
+                            away.y * KNOCKBACK_VELOCITY.1
+                        } else {
+                            KNOCKBACK_VELOCITY.1
+                        },
+                    ));
+                    let _ = invulnerabilities
+                        .insert(entity, Invulnerability::new(INVULNERABILITY_DURATION));
                 }
             }
         }
@@ -0,0 +1,68 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use amethyst::{
+    config::Config,
+    core::Time,
+    ecs::{Read, System, Write},
+};
+
+use crate::resources::GameplayConfig;
+
+/// Time between two checks of the configuration file, in seconds.
+const CHECK_INTERVAL: f32 = 0.5;
+
+/// Reloads the `GameplayConfig` whenever its file is modified.
+///
+/// An invalid file is reported and ignored, the game keeps the last valid configuration until
+/// the file is fixed.
+pub struct GameplayConfigReloadSystem {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    next_check: f32,
+}
+
+impl GameplayConfigReloadSystem {
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modification_time(&path);
+        GameplayConfigReloadSystem {
+            path,
+            modified,
+            next_check: CHECK_INTERVAL,
+        }
+    }
+}
+
+impl<'s> System<'s> for GameplayConfigReloadSystem {
+    type SystemData = (Read<'s, Time>, Write<'s, GameplayConfig>);
+
+    fn run(&mut self, (time, mut config): Self::SystemData) {
+        self.next_check -= time.delta_real_seconds();
+        if self.next_check > 0. {
+            return;
+        }
+        self.next_check = CHECK_INTERVAL;
+
+        let modified = modification_time(&self.path);
+        if modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        match GameplayConfig::load(&self.path) {
+            Ok(new_config) => {
+                info!("Reloaded {}", self.path.display());
+                *config = new_config;
+            }
+            Err(error) => warn!("Failed to reload {}: {}", self.path.display(), error),
+        }
+    }
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -3,7 +3,7 @@ use amethyst::{
     ecs::{Entities, Join, ReadStorage, System, WriteStorage},
 };
 
-use std::f32::consts::PI;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
 use crate::components::{Direction, Directions};
 
@@ -19,7 +19,21 @@ impl<'s> System<'s> for DirectionSystem {
     fn run(&mut self, (entities, directions, mut transforms): Self::SystemData) {
         // Iterate over entities having direction and transform components
         for (_, direction, transform) in (&entities, &directions, &mut transforms).join() {
-            if direction.x == direction.default_x {
+            if direction.y == Directions::Up || direction.y == Directions::Down {
+                // Entities going up or down, such as bullets, are tilted towards where they go:
+                // by 45 deg when they also go sideways, by 90 deg otherwise.
+                let flipped =
+                    direction.x != Directions::Neutral && direction.x != direction.default_x;
+                let mut tilt = if direction.x == Directions::Neutral {
+                    FRAC_PI_2
+                } else {
+                    FRAC_PI_4
+                };
+                if (direction.y == Directions::Down) != flipped {
+                    tilt = -tilt;
+                }
+                transform.set_rotation_euler(0., if flipped { PI } else { 0. }, tilt);
+            } else if direction.x == direction.default_x {
                 // Rotate by 0 deg along y-axis if direction is right
                 // as right is the default direction
                 transform.set_rotation_y_axis(0.);
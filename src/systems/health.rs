@@ -0,0 +1,161 @@
+use amethyst::{
+    core::{Time, Transform},
+    ecs::{
+        prelude::World, Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System,
+        SystemData, Write, WriteStorage,
+    },
+    renderer::resources::Tint,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::{Bullet, Damage, Health, Invulnerability, Marine, Owner},
+    entities::show_explosion,
+    resources::{AssetType, CollisionEvent, Context, DeathEvent, PrefabList},
+};
+
+/// Time during which the marine can't be hurt again, in seconds.
+pub(super) const INVULNERABILITY_DURATION: f32 = 1.5;
+
+#[derive(Default)]
+pub struct DamageSystem {
+    reader_id: Option<ReaderId<CollisionEvent>>,
+}
+
+/// Applies the damage of the projectiles to the entities they hit.
+///
+/// Every entity running out of health is reported with a `DeathEvent`. Enemies blow up and are
+/// removed straight away, while the marine is left to play its dying animation.
+impl<'s> System<'s> for DamageSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Invulnerability>,
+        ReadStorage<'s, Damage>,
+        ReadStorage<'s, Bullet>,
+        ReadStorage<'s, Owner>,
+        ReadStorage<'s, Marine>,
+        ReadStorage<'s, Transform>,
+        Read<'s, EventChannel<CollisionEvent>>,
+        Write<'s, EventChannel<DeathEvent>>,
+        ReadExpect<'s, PrefabList>,
+        ReadExpect<'s, LazyUpdate>,
+        ReadExpect<'s, Context>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CollisionEvent>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut healths,
+            mut invulnerabilities,
+            damages,
+            bullets,
+            owners,
+            marines,
+            transforms,
+            collision_events,
+            mut death_events,
+            prefab_list,
+            lazy_update,
+            ctx,
+        ) = data;
+
+        // A bullet only hurts the first thing it hits, even if it touches several at once.
+        let mut spent_bullets = Vec::new();
+
+        for event in collision_events.read(self.reader_id.as_mut().unwrap()) {
+            let bullet = event.entity;
+            let entity = match event.other {
+                Some(other) => other,
+                None => continue,
+            };
+            let damage = match damages.get(bullet) {
+                Some(damage) if bullets.contains(bullet) && !spent_bullets.contains(&bullet) => {
+                    damage
+                }
+                _ => continue,
+            };
+
+            if let (Some(health), Some(transform)) =
+                (healths.get_mut(entity), transforms.get(entity))
+            {
+                if health.is_dead() || invulnerabilities.contains(entity) {
+                    continue;
+                }
+                spent_bullets.push(bullet);
+                health.damage(damage.amount);
+
+                if health.is_dead() {
+                    let translation = transform.translation();
+                    death_events.single_write(DeathEvent {
+                        entity,
+                        killer: owners.get(bullet).map(|owner| owner.entity),
+                    });
+                    if !marines.contains(entity) {
+                        let small_explosion_prefab_handle =
+                            { prefab_list.get(AssetType::SmallExplosion).unwrap().clone() };
+                        show_explosion(
+                            &entities,
+                            small_explosion_prefab_handle,
+                            translation.x,
+                            translation.y,
+                            &lazy_update,
+                            &ctx,
+                        );
+                        let _ = entities.delete(entity);
+                    }
+                } else if marines.contains(entity) {
+                    // Same grace period as when an enemy runs into the marine.
+                    let _ = invulnerabilities
+                        .insert(entity, Invulnerability::new(INVULNERABILITY_DURATION));
+                }
+            }
+        }
+    }
+}
+
+/// Number of times per second an invulnerable entity blinks.
+const BLINK_FREQUENCY: f32 = 10.;
+
+pub struct InvulnerabilitySystem;
+
+/// Counts down the invulnerability of the entities which got hurt and makes them blink
+/// until they can be hurt again.
+impl<'s> System<'s> for InvulnerabilitySystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Invulnerability>,
+        WriteStorage<'s, Tint>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (entities, mut invulnerabilities, mut tints, time): Self::SystemData) {
+        let mut expired = Vec::new();
+
+        for (entity, invulnerability, tint) in
+            (&entities, &mut invulnerabilities, &mut tints).join()
+        {
+            invulnerability.remaining -= time.delta_seconds();
+            if invulnerability.remaining <= 0. {
+                tint.0.alpha = 1.;
+                expired.push(entity);
+            } else {
+                let visible = (invulnerability.remaining * BLINK_FREQUENCY) as u32 % 2 == 0;
+                tint.0.alpha = if visible { 1. } else { 0.2 };
+            }
+        }
+
+        for entity in expired {
+            invulnerabilities.remove(entity);
+        }
+    }
+}
@@ -1,9 +1,24 @@
 use amethyst::{
-    ecs::{Join, Read, System, WriteStorage},
+    core::math::Vector2,
+    ecs::{Join, Read, ReadStorage, System, WriteStorage},
     input::{InputHandler, StringBindings},
 };
 
-use crate::components::{Collider, Direction, Directions, Marine, MarineState};
+use crate::{
+    components::{Collider, Direction, Directions, Health, Marine, MarineState},
+    resources::{GameplayConfig, STEP_SECONDS},
+};
+
+/// Turns an aim axis value into -1, 0 or 1, ignoring the small movements of analog sticks.
+fn aim_step(value: f32) -> f32 {
+    if value > 0.5 {
+        1.
+    } else if value < -0.5 {
+        -1.
+    } else {
+        0.
+    }
+}
 
 pub struct MarineInputSystem;
 
@@ -11,22 +26,35 @@ impl<'s> System<'s> for MarineInputSystem {
     type SystemData = (
         WriteStorage<'s, Direction>,
         WriteStorage<'s, Marine>,
-        WriteStorage<'s, Collider>,
+        ReadStorage<'s, Health>,
+        ReadStorage<'s, Collider>,
         Read<'s, InputHandler<StringBindings>>,
+        Read<'s, GameplayConfig>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut dir, mut marines, mut colliders, input) = data;
+        let (mut dir, mut marines, healths, colliders, input, config) = data;
 
-        for (dir, marine, collider) in (&mut dir, &mut marines, &mut colliders).join() {
+        for (dir, marine, health, collider) in (&mut dir, &mut marines, &healths, &colliders).join()
+        {
             let run_input = input.axis_value("run").expect("Run action exists");
             let jump_input = input.action_is_down("jump").expect("Jump action exists");
-            let shoot_input = input.action_is_down("shoot").expect("Shoot action exists");
 
+            // Only a new press starts a jump, holding the key down doesn't jump again on landing.
+            if jump_input && !marine.is_jump_held {
+                marine.jump_buffer = config.marine.jump_buffer_time;
+            } else {
+                marine.jump_buffer = (marine.jump_buffer - STEP_SECONDS).max(0.);
+            }
+            marine.is_jump_held = jump_input;
+
+            // Shooting doesn't depend on the movements, it is handled by the AttackSystem.
             // TODO: check simultaneous button press
-            marine.state = if !collider.is_collidable {
+            marine.state = if health.is_dead() {
                 MarineState::Dying
-            } else if jump_input || !collider.on_ground {
+            } else if marine.is_knocked_back {
+                MarineState::Hurt
+            } else if marine.jump_buffer > 0. || !collider.on_ground {
                 MarineState::Jumping
             } else if run_input > 0. {
                 dir.x = Directions::Right;
@@ -34,11 +62,29 @@ impl<'s> System<'s> for MarineInputSystem {
             } else if run_input < 0. {
                 dir.x = Directions::Left;
                 MarineState::Running
-            } else if shoot_input && marine.state != MarineState::Shooting && !marine.is_shooting {
-                MarineState::Shooting
             } else {
                 MarineState::Idling
+            };
+
+            // Aim in one of eight directions, straight ahead when not aiming at all.
+            let aim_x = aim_step(input.axis_value("aim_x").expect("Aim x axis exists"));
+            let aim_y = aim_step(input.axis_value("aim_y").expect("Aim y axis exists"));
+            // The marine turns around to aim behind it, unless it's running the other way.
+            if aim_x != 0. && matches!(marine.state, MarineState::Idling | MarineState::Jumping) {
+                dir.x = if aim_x > 0. {
+                    Directions::Right
+                } else {
+                    Directions::Left
+                };
             }
+            let facing = if dir.x == Directions::Left { -1. } else { 1. };
+            marine.aim = if aim_y == 0. {
+                Vector2::new(facing, 0.)
+            } else if aim_x == 0. {
+                Vector2::new(0., aim_y)
+            } else {
+                Vector2::new(facing, aim_y).normalize()
+            };
         }
     }
 }
@@ -1,9 +1,12 @@
 use amethyst::{
     core::math::Vector2,
-    ecs::{Join, ReadStorage, System, WriteStorage},
+    ecs::{Join, Read, ReadStorage, System, WriteStorage},
 };
 
-use crate::components::{Collider, Direction, Marine, MarineState, Motion};
+use crate::{
+    components::{Collider, Direction, Marine, MarineState, Motion},
+    resources::{GameplayConfig, STEP_SECONDS},
+};
 
 pub struct KinematicsSystem;
 
@@ -16,8 +19,7 @@ impl<'s> System<'s> for KinematicsSystem {
         for (collider, motion) in (&mut colliders, &motions).join() {
             let bbox = &mut collider.bounding_box;
             bbox.old_position = bbox.position;
-            bbox.position.x += motion.velocity.x;
-            bbox.position.y += motion.velocity.y;
+            bbox.position += motion.velocity * STEP_SECONDS;
 
             let hbox = &mut collider.hit_box;
             hbox.old_position = hbox.position;
@@ -32,44 +34,79 @@ impl<'s> System<'s> for MarineKinematicsSystem {
     type SystemData = (
         WriteStorage<'s, Collider>,
         ReadStorage<'s, Direction>,
-        ReadStorage<'s, Marine>,
+        WriteStorage<'s, Marine>,
         WriteStorage<'s, Motion>,
+        Read<'s, GameplayConfig>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut colliders, dirs, marines, mut motions) = data;
+        let (mut colliders, dirs, mut marines, mut motions, config) = data;
+        let config = &config.marine;
 
         for (collider, dir, marine, motion) in
-            (&mut colliders, &dirs, &marines, &mut motions).join()
+            (&mut colliders, &dirs, &mut marines, &mut motions).join()
         {
-            let mut acceleration = Vector2::new(0., 0.);
+            let acceleration;
+            let mut max_speed = config.max_ground_speed;
+            if collider.on_ground {
+                marine.coyote_time = config.coyote_time;
+            } else {
+                marine.coyote_time = (marine.coyote_time - STEP_SECONDS).max(0.);
+            }
             match marine.state {
                 MarineState::Idling => {
-                    let acceleration_x = if motion.velocity.x != 0. { -0.6 } else { 0. };
-                    acceleration = Vector2::new(acceleration_x, -0.6);
+                    let acceleration_x = if motion.velocity.x != 0. {
+                        -config.ground_friction
+                    } else {
+                        0.
+                    };
+                    acceleration = Vector2::new(acceleration_x, -config.gravity);
                 }
                 MarineState::Running => {
-                    acceleration = Vector2::new(0.6, -0.6);
+                    acceleration = Vector2::new(config.run_acceleration, -config.gravity);
                 }
                 MarineState::Jumping => {
-                    if collider.on_ground {
-                        motion.velocity.y = 14.;
+                    if marine.jump_buffer > 0. && marine.coyote_time > 0. {
+                        motion.velocity.y = config.jump_velocity;
+                        collider.on_ground = false;
+                        marine.jump_buffer = 0.;
+                        marine.coyote_time = 0.;
+                    } else if !marine.is_jump_held {
+                        // The jump key was released early, the marine doesn't go as high.
+                        motion.velocity.y = motion.velocity.y.min(config.jump_cut_velocity);
+                    }
+                    let acceleration_x = if motion.velocity.x != 0. {
+                        -config.air_drag
+                    } else {
+                        0.
+                    };
+                    acceleration = Vector2::new(acceleration_x, -config.gravity);
+                    max_speed = config.max_air_speed;
+                }
+                MarineState::Hurt => {
+                    // The player has no control over the marine until it lands back on the
+                    // ground, so the knockback velocity is left untouched.
+                    if let Some(knockback) = marine.knockback.take() {
+                        motion.velocity = knockback;
                         collider.on_ground = false;
+                    } else if collider.on_ground {
+                        marine.is_knocked_back = false;
                     }
-                    let acceleration_x = if motion.velocity.x != 0. { -0.06 } else { 0. };
-                    acceleration = Vector2::new(acceleration_x, -0.6);
+                    motion.velocity.y -= config.gravity * STEP_SECONDS;
+                    continue;
                 }
                 MarineState::Dying => {
+                    // Let the marine fall through the floor, out of the level.
+                    collider.is_collidable = false;
                     if collider.on_ground {
                         motion.velocity.x = 0.;
-                        motion.velocity.y = 8.;
+                        motion.velocity.y = config.death_bounce_velocity;
                         collider.on_ground = false;
                     }
-                    acceleration = Vector2::new(0., -0.6);
+                    acceleration = Vector2::new(0., -config.gravity);
                 }
-                _ => {}
             }
-            motion.update_velocity(acceleration, dir, 0., marine.max_ground_speed);
+            motion.update_velocity(acceleration, dir, 0., max_speed, STEP_SECONDS);
         }
     }
 }
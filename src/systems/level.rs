@@ -0,0 +1,57 @@
+use amethyst::ecs::{Join, ReadStorage, System, Write};
+
+use crate::{
+    components::{Checkpoint, Collider, Exit, Marine},
+    resources::{Campaign, Respawn},
+};
+
+pub struct ExitSystem;
+
+/// Flags the current level as complete once the marine reaches one of its exits.
+impl<'s> System<'s> for ExitSystem {
+    type SystemData = (
+        ReadStorage<'s, Marine>,
+        ReadStorage<'s, Exit>,
+        ReadStorage<'s, Collider>,
+        Write<'s, Campaign>,
+    );
+
+    fn run(&mut self, (marines, exits, colliders, mut campaign): Self::SystemData) {
+        for (_, marine_collider) in (&marines, &colliders).join() {
+            for (_, exit_collider) in (&exits, &colliders).join() {
+                if exit_collider.can_collide_with(marine_collider)
+                    && marine_collider.is_overlapping_with(exit_collider, false)
+                {
+                    campaign.exit_reached = true;
+                }
+            }
+        }
+    }
+}
+
+pub struct CheckpointSystem;
+
+/// Moves the respawn point of the marine to the last checkpoint it went through.
+impl<'s> System<'s> for CheckpointSystem {
+    type SystemData = (
+        ReadStorage<'s, Marine>,
+        ReadStorage<'s, Checkpoint>,
+        ReadStorage<'s, Collider>,
+        Write<'s, Respawn>,
+    );
+
+    fn run(&mut self, (marines, checkpoints, colliders, mut respawn): Self::SystemData) {
+        for (_, marine_collider) in (&marines, &colliders).join() {
+            for (_, checkpoint_collider) in (&checkpoints, &colliders).join() {
+                let position = checkpoint_collider.bounding_box.position;
+                if respawn.position != position
+                    && checkpoint_collider.can_collide_with(marine_collider)
+                    && marine_collider.is_overlapping_with(checkpoint_collider, false)
+                {
+                    info!("Checkpoint reached at x = {:.2}", position.x);
+                    respawn.position = position;
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,19 @@
 mod animation;
 mod attack;
+mod behavior;
 mod collision;
+mod config;
 mod death;
 mod direction;
-mod flier;
+mod health;
 mod input;
 mod kinematics;
+mod level;
 mod parallax;
-mod pincer;
+mod perception;
+mod pickup;
+mod resize;
+mod score;
 mod transformation;
 mod ui;
 
@@ -18,20 +24,28 @@ pub use self::animation::FlierAnimationSystem;
 pub use self::animation::MarineAnimationSystem;
 pub use self::animation::PincerAnimationSystem;
 pub use self::attack::AttackSystem;
+pub use self::behavior::BehaviorSystem;
 pub use self::collision::BulletCollisionSystem;
 pub use self::collision::CollisionSystem;
-pub use self::collision::FlierCollisionSystem;
+pub use self::collision::EnemyCollisionSystem;
 pub use self::collision::MarineCollisionSystem;
-pub use self::collision::PincerCollisionSystem;
+pub use self::config::GameplayConfigReloadSystem;
 pub use self::death::MarineDeathSystem;
 pub use self::direction::DirectionSystem;
-pub use self::flier::FlierAiSystem;
+pub use self::health::DamageSystem;
+pub use self::health::InvulnerabilitySystem;
 pub use self::input::MarineInputSystem;
 pub use self::kinematics::KinematicsSystem;
 pub use self::kinematics::MarineKinematicsSystem;
+pub use self::level::CheckpointSystem;
+pub use self::level::ExitSystem;
 pub use self::parallax::ParallaxSystem;
-pub use self::pincer::PincerAiSystem;
+pub use self::perception::PerceptionSystem;
+pub use self::pickup::PickupSystem;
+pub use self::resize::ScreenResizeSystem;
+pub use self::score::ScoreSystem;
 pub use self::transformation::BulletTransformationSystem;
 pub use self::transformation::CameraTransformationSystem;
+pub use self::transformation::InterpolationSystem;
 pub use self::transformation::TransformationSystem;
 pub use self::ui::*;
@@ -3,37 +3,38 @@ use amethyst::{
     ecs::{Join, ReadStorage, System, WriteStorage},
 };
 
-use crate::components::{Collider, Marine, Motion, Parallax};
+use crate::components::{Marine, Parallax};
 
+/// Scrolls the background layers as the marine moves, the farthest ones the slowest.
 #[derive(Default)]
-pub struct ParallaxSystem;
+pub struct ParallaxSystem {
+    /// Where the marine was drawn during the previous frame.
+    last_marine_x: Option<f32>,
+}
 
 impl<'s> System<'s> for ParallaxSystem {
     type SystemData = (
         ReadStorage<'s, Parallax>,
         ReadStorage<'s, Marine>,
-        ReadStorage<'s, Motion>,
-        ReadStorage<'s, Collider>,
         WriteStorage<'s, Transform>,
     );
     fn run(&mut self, data: Self::SystemData) {
-        let (parallaxes, marines, motions, colliders, mut transforms) = data;
-        let mut marine_velocity_x = 0.;
-        let mut marine_moved = false;
-
-        for (_, motion, collider) in (&marines, &motions, &colliders).join() {
-            marine_velocity_x = motion.velocity.x;
-            let bbox = &collider.bounding_box;
-            marine_moved = (bbox.position.x - bbox.old_position.x).abs() > std::f32::EPSILON;
-        }
+        let (parallaxes, marines, mut transforms) = data;
+        let marine_x = (&marines, &transforms)
+            .join()
+            .map(|(_, transform)| transform.translation().x)
+            .next();
 
-        for (_, transform) in (&parallaxes, &mut transforms).join() {
-            if marine_moved {
+        // Nothing scrolls on the frame a marine respawns, it didn't move through the level.
+        if let (Some(marine_x), Some(last_marine_x)) = (marine_x, self.last_marine_x) {
+            let marine_shift_x = marine_x - last_marine_x;
+            for (_, transform) in (&parallaxes, &mut transforms).join() {
                 transform.set_translation_x(
                     transform.translation().x
-                        + marine_velocity_x / (transform.translation().z.abs() * 4. / 10.),
+                        + marine_shift_x / (transform.translation().z.abs() * 4. / 10.),
                 );
             }
         }
+        self.last_marine_x = marine_x;
     }
 }
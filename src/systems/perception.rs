@@ -0,0 +1,118 @@
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Entities, Entity, Join, Read, ReadStorage, System, WriteStorage},
+};
+
+use crate::{
+    components::{Collider, Direction, Directions, GenericBox, Health, Marine, Perception},
+    resources::{SpatialGrid, STEP_SECONDS},
+};
+
+/// Returns whether none of the static colliders of the map stands between `from` and `to`.
+fn is_in_line_of_sight(
+    from: Vector2<f32>,
+    to: Vector2<f32>,
+    grid: &SpatialGrid,
+    colliders: &ReadStorage<Collider>,
+    candidates: &mut Vec<Entity>,
+) -> bool {
+    // A point moving from one end to the other, swept against the walls like a bullet.
+    let mut ray = GenericBox::new(0., 0.);
+    ray.old_position = from;
+    ray.position = to;
+
+    grid.query_static(
+        from.zip_map(&to, f32::min),
+        from.zip_map(&to, f32::max),
+        candidates,
+    );
+    candidates
+        .iter()
+        .filter_map(|&entity| colliders.get(entity))
+        .all(|collider| ray.sweep(&collider.bounding_box).is_none())
+}
+
+pub struct PerceptionSystem;
+
+/// Lets the enemies notice the marine on their own, and forget about it once it has been out of
+/// their senses for longer than their attention span.
+///
+/// The marine is seen when it's within the sight range and cone of an enemy, with none of the
+/// static colliders of the map in between. It's heard when it's within the hearing radius,
+/// whatever stands in between.
+impl<'s> System<'s> for PerceptionSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Perception>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Direction>,
+        ReadStorage<'s, Marine>,
+        ReadStorage<'s, Health>,
+        Read<'s, SpatialGrid>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut perceptions, colliders, directions, marines, healths, grid) = data;
+
+        let marines: Vec<(Entity, Vector2<f32>)> = (&entities, &marines, &colliders, &healths)
+            .join()
+            .filter(|(_, _, _, health)| !health.is_dead())
+            .map(|(entity, _, collider, _)| (entity, collider.bounding_box.position))
+            .collect();
+        let mut candidates = Vec::new();
+
+        for (perception, collider, direction) in (&mut perceptions, &colliders, &directions).join()
+        {
+            let config = perception.config;
+            let position = collider.bounding_box.position;
+            let facing = match direction.x {
+                Directions::Neutral => direction.default_x,
+                facing => facing,
+            };
+            let facing = if facing == Directions::Left { -1. } else { 1. };
+            let min_cos_angle = (config.sight_angle / 2.).to_radians().cos();
+
+            let mut noticed = None;
+            for &(marine, marine_position) in marines.iter() {
+                let offset = marine_position - position;
+                let distance = offset.norm();
+                let is_seen = distance <= config.sight_range
+                    && (distance <= std::f32::EPSILON
+                        || offset.x * facing / distance >= min_cos_angle)
+                    && is_in_line_of_sight(
+                        position,
+                        marine_position,
+                        &grid,
+                        &colliders,
+                        &mut candidates,
+                    );
+                let is_heard = distance <= config.hearing_radius;
+                // Stick to the current target when several marines are around.
+                if (is_seen || is_heard) && (noticed.is_none() || perception.target == Some(marine))
+                {
+                    noticed = Some((marine, is_seen));
+                }
+            }
+
+            match noticed {
+                Some((marine, is_seen)) => {
+                    perception.alert(marine);
+                    perception.sees_target = is_seen;
+                }
+                None => {
+                    perception.sees_target = false;
+                    perception.interest = (perception.interest - STEP_SECONDS).max(0.);
+                    if perception.interest <= 0. {
+                        perception.target = None;
+                    }
+                }
+            }
+            if perception
+                .target
+                .map_or(false, |target| !entities.is_alive(target))
+            {
+                perception.target = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use amethyst::ecs::{Entities, Join, ReadStorage, System, WriteStorage};
+
+use crate::components::{Collider, Health, Marine, Pickup, Weapon};
+
+pub struct PickupSystem;
+
+/// Collects every pickup overlapped by the marine.
+impl<'s> System<'s> for PickupSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Marine>,
+        ReadStorage<'s, Pickup>,
+        ReadStorage<'s, Collider>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Weapon>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, marines, pickups, colliders, mut healths, mut weapons): Self::SystemData,
+    ) {
+        for (_, marine_collider, health, mut weapon) in
+            (&marines, &colliders, &mut healths, (&mut weapons).maybe()).join()
+        {
+            for (entity, pickup, pickup_collider) in (&entities, &pickups, &colliders).join() {
+                if pickup_collider.can_collide_with(marine_collider)
+                    && marine_collider.is_overlapping_with(pickup_collider, false)
+                {
+                    info!("Marine collected a {} pickup", pickup.kind);
+                    match pickup.kind.as_ref() {
+                        "health" => health.heal(1),
+                        // Only the weapon in the marine's hands is refilled.
+                        "ammo" => {
+                            if let Some(weapon) = weapon.as_mut() {
+                                weapon.refill();
+                            }
+                        }
+                        _ => {}
+                    }
+                    let _ = entities.delete(entity);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use amethyst::{
+    ecs::{Join, ReadExpect, System, WriteExpect, WriteStorage},
+    renderer::camera::Camera,
+    window::ScreenDimensions,
+};
+
+use crate::resources::Context;
+
+pub struct ScreenResizeSystem;
+
+/// Keeps the `Context` and the camera projection in sync with the size of the window.
+impl<'s> System<'s> for ScreenResizeSystem {
+    type SystemData = (
+        ReadExpect<'s, ScreenDimensions>,
+        WriteExpect<'s, Context>,
+        WriteStorage<'s, Camera>,
+    );
+
+    fn run(&mut self, (screen_dimensions, mut ctx, mut cameras): Self::SystemData) {
+        let (width, height) = (screen_dimensions.width(), screen_dimensions.height());
+        if (ctx.screen_width - width).abs() < std::f32::EPSILON
+            && (ctx.screen_height - height).abs() < std::f32::EPSILON
+        {
+            return;
+        }
+
+        ctx.resize(&screen_dimensions);
+        for camera in (&mut cameras).join() {
+            *camera = Camera::standard_2d(width, height);
+        }
+    }
+}
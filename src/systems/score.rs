@@ -0,0 +1,57 @@
+use amethyst::{
+    ecs::{prelude::World, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::{Bounty, Marine, Score},
+    resources::{Campaign, DeathEvent},
+};
+
+#[derive(Default)]
+pub struct ScoreSystem {
+    reader_id: Option<ReaderId<DeathEvent>>,
+}
+
+/// Credits every kill to the entity which made it, along with the bounty of the victim.
+///
+/// The points earned by the marine are also added to the score of the campaign, which outlives
+/// the marine itself.
+impl<'s> System<'s> for ScoreSystem {
+    type SystemData = (
+        WriteStorage<'s, Score>,
+        ReadStorage<'s, Bounty>,
+        ReadStorage<'s, Marine>,
+        Read<'s, EventChannel<DeathEvent>>,
+        Write<'s, Campaign>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DeathEvent>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut scores, bounties, marines, death_events, mut campaign) = data;
+
+        for event in death_events.read(self.reader_id.as_mut().unwrap()) {
+            let killer = match event.killer {
+                Some(killer) => killer,
+                None => continue,
+            };
+            // The victim was only marked for deletion, its bounty is still there.
+            let points = bounties.get(event.entity).map_or(0, |bounty| bounty.points);
+            if let Some(score) = scores.get_mut(killer) {
+                score.kills += 1;
+                score.points += points;
+            }
+            if marines.contains(killer) {
+                campaign.score += points;
+            }
+        }
+    }
+}
@@ -1,34 +1,28 @@
 use amethyst::{
     core::Transform,
-    ecs::{Join, ReadExpect, ReadStorage, System, WriteStorage},
+    ecs::{Join, Read, ReadExpect, ReadStorage, System, WriteStorage},
 };
 
 use crate::{
     components::{Bullet, Collidee, Collider, Marine, Motion, Subject},
-    resources::Context,
+    resources::{Context, FixedStep},
 };
 
 pub struct TransformationSystem;
 
+/// Resolves the collisions found during the physics step. The sprites are moved to the new
+/// positions by the `InterpolationSystem`.
 impl<'s> System<'s> for TransformationSystem {
     type SystemData = (
         WriteStorage<'s, Collider>,
         WriteStorage<'s, Collidee>,
         WriteStorage<'s, Motion>,
-        WriteStorage<'s, Transform>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut colliders, mut collidees, mut motions, mut transforms) = data;
-
-        for (collider, collidee, motion, transform) in (
-            &mut colliders,
-            &mut collidees,
-            &mut motions,
-            &mut transforms,
-        )
-            .join()
-        {
+        let (mut colliders, mut collidees, mut motions) = data;
+
+        for (collider, collidee, motion) in (&mut colliders, &mut collidees, &mut motions).join() {
             let bbox = &mut collider.bounding_box;
             let velocity = &mut motion.velocity;
 
@@ -47,13 +41,35 @@ impl<'s> System<'s> for TransformationSystem {
             if collidee.vertical.is_none() && velocity.y != 0. {
                 collider.on_ground = false;
             }
-            transform.set_translation_x(bbox.position.x);
-            transform.set_translation_y(bbox.position.y);
             collider.set_hit_box_position(*velocity);
         }
     }
 }
 
+pub struct InterpolationSystem;
+
+/// Draws the moving entities between their positions of the last two physics steps, so that
+/// they move smoothly whatever the frame rate.
+impl<'s> System<'s> for InterpolationSystem {
+    type SystemData = (
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Motion>,
+        WriteStorage<'s, Transform>,
+        Read<'s, FixedStep>,
+    );
+
+    fn run(&mut self, (colliders, motions, mut transforms, fixed_step): Self::SystemData) {
+        let alpha = fixed_step.alpha();
+
+        for (collider, _, transform) in (&colliders, &motions, &mut transforms).join() {
+            let bbox = &collider.bounding_box;
+            let position = bbox.old_position + (bbox.position - bbox.old_position) * alpha;
+            transform.set_translation_x(position.x);
+            transform.set_translation_y(position.y);
+        }
+    }
+}
+
 pub struct BulletTransformationSystem;
 
 impl<'s> System<'s> for BulletTransformationSystem {
@@ -79,18 +95,17 @@ impl<'s> System<'s> for CameraTransformationSystem {
     );
 
     fn run(&mut self, (marines, subject_tags, mut transforms, ctx): Self::SystemData) {
-        let mut marine_x = 0.;
-        let map_width = ctx.map_width;
-        let background_width = ctx.bg_width;
+        let mut marine_x = None;
 
         for (_marine, transform) in (&marines, &transforms).join() {
-            marine_x = transform.translation().x;
+            marine_x = Some(transform.translation().x);
         }
 
         for (_subject_tag, transform) in (&subject_tags, &mut transforms).join() {
-            if marine_x >= background_width && marine_x <= map_width - background_width {
-                transform.set_translation_x(marine_x);
+            if let Some(marine_x) = marine_x {
+                transform.set_translation_x(ctx.clamp_camera_x(marine_x));
             }
+            transform.set_translation_y(ctx.camera_y());
         }
     }
 }
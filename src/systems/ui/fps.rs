@@ -23,10 +23,12 @@ impl<'a> System<'a> for UiFpsSystem {
     );
 
     fn run(&mut self, (time, mut ui_text, fps_counter, finder): Self::SystemData) {
-        if self.fps_display.is_none() {
-            if let Some(fps_entity) = finder.find("fps_text") {
-                self.fps_display = Some(fps_entity);
-            }
+        // The label is recreated every time a level starts, look it up again once it's gone.
+        if self
+            .fps_display
+            .map_or(true, |fps_entity| ui_text.get(fps_entity).is_none())
+        {
+            self.fps_display = finder.find("fps_text");
         }
         if let Some(fps_entity) = self.fps_display {
             if let Some(fps_display) = ui_text.get_mut(fps_entity) {
@@ -4,7 +4,10 @@ use amethyst::{
     ui::{UiFinder, UiText},
 };
 
-use crate::components::Marine;
+use crate::{
+    components::{Health, Marine, Weapon},
+    resources::Campaign,
+};
 
 #[derive(Default)]
 pub struct UiPlayerSystem {
@@ -14,8 +17,11 @@ pub struct UiPlayerSystem {
 impl<'a> System<'a> for UiPlayerSystem {
     type SystemData = (
         ReadStorage<'a, Marine>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Weapon>,
         ReadStorage<'a, Transform>,
         Read<'a, Time>,
+        Read<'a, Campaign>,
         WriteStorage<'a, UiText>,
         UiFinder<'a>,
     );
@@ -23,19 +29,32 @@ impl<'a> System<'a> for UiPlayerSystem {
     fn run(&mut self, data: Self::SystemData) {
         // let (time, mut ui_text, marines, finder) = data;
 
-        let (marines, transforms, time, mut ui_text, finder) = data;
+        let (marines, healths, weapons, transforms, time, campaign, mut ui_text, finder) = data;
 
-        for (_marine, transform) in (&marines, &transforms).join() {
-            if self.player_display.is_none() {
-                if let Some(player_entity) = finder.find("player_text") {
-                    self.player_display = Some(player_entity);
-                }
+        for (_marine, health, weapon, transform) in
+            (&marines, &healths, (&weapons).maybe(), &transforms).join()
+        {
+            // The label is recreated every time a level starts, look it up again once it's gone.
+            if self
+                .player_display
+                .map_or(true, |player_entity| ui_text.get(player_entity).is_none())
+            {
+                self.player_display = finder.find("player_text");
             }
             if let Some(player_entity) = self.player_display {
                 if let Some(player_display) = ui_text.get_mut(player_entity) {
                     if time.frame_number() % 20 == 0 {
+                        let weapon = weapon.map_or_else(String::new, |weapon| match weapon.ammo {
+                            Some(ammo) => format!(" {} {}", weapon.definition.name, ammo),
+                            None => format!(" {}", weapon.definition.name),
+                        });
                         player_display.text = format!(
-                            "Player: x = {:.2}, y = {:.2}",
+                            "Score: {} Lives: {} HP: {}/{}{} x = {:.2}, y = {:.2}",
+                            campaign.score,
+                            campaign.lives_left,
+                            health.current,
+                            health.max,
+                            weapon,
                             transform.translation().x,
                             transform.translation().y
                         );